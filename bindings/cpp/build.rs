@@ -0,0 +1,22 @@
+use std::env;
+
+fn main() {
+    let major = env::var("CARGO_PKG_VERSION_MAJOR").unwrap();
+    let minor = env::var("CARGO_PKG_VERSION_MINOR").unwrap();
+    // Before v1.0, a new minor version may break the ABI (like a new major version).
+    // This must agree with the `MK_PASS_SOVERSION` in CMakeLists.txt.
+    let so_version = if major == "0" {
+        format!("0.{minor}")
+    } else {
+        major
+    };
+    let vendor = env::var("CARGO_CFG_TARGET_VENDOR").unwrap_or_default();
+    let family = env::var("CARGO_CFG_TARGET_FAMILY").unwrap_or_default();
+    if vendor == "apple" {
+        println!(
+            "cargo::rustc-cdylib-link-arg=-Wl,-install_name,@rpath/libmk_pass.{so_version}.dylib"
+        );
+    } else if family.split(',').any(|f| f == "unix") {
+        println!("cargo::rustc-cdylib-link-arg=-Wl,-soname,libmk_pass.so.{so_version}");
+    }
+}
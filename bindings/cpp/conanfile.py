@@ -32,6 +32,8 @@ class mk_passRecipe(ConanFile):
         # Sources are located in the same place as this recipe, copy them to the recipe
         exports_sources = (
             "bindings/cpp/CMakeLists.txt",
+            "bindings/cpp/cmake/*",
+            "bindings/cpp/build.rs",
             "bindings/cpp/src/*",
             "bindings/cpp/include/*",
             "bindings/cpp/Cargo.toml",
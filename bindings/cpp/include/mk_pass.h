@@ -0,0 +1,596 @@
+#ifndef MK_PASS_C_H_
+#define MK_PASS_C_H_
+
+/* Generated with cbindgen:0.29.2 */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+
+/**
+ * The status returned by a function that writes to a given buffer.
+ */
+enum MkPassStatus
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * The function succeeded.
+     */
+    MK_PASS_STATUS_OK,
+    /**
+     * A given pointer was null.
+     */
+    MK_PASS_STATUS_NULL_POINTER,
+    /**
+     * The given buffer's capacity is less than the required size.
+     *
+     * Nothing was written to the buffer.
+     */
+    MK_PASS_STATUS_BUFFER_TOO_SMALL,
+    /**
+     * A given string is not valid UTF-8.
+     */
+    MK_PASS_STATUS_INVALID_UTF8,
+};
+#ifndef __cplusplus
+typedef uint8_t MkPassStatus;
+#endif // __cplusplus
+
+/**
+ * A kind of character used in a password.
+ */
+enum MkPassCharKind
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * The uppercase letters.
+     */
+    MK_PASS_CHAR_KIND_UPPERCASE,
+    /**
+     * The lowercase letters.
+     */
+    MK_PASS_CHAR_KIND_LOWERCASE,
+    /**
+     * The decimal integers.
+     */
+    MK_PASS_CHAR_KIND_DECIMAL,
+    /**
+     * The special characters.
+     */
+    MK_PASS_CHAR_KIND_SPECIAL,
+};
+#ifndef __cplusplus
+typedef uint8_t MkPassCharKind;
+#endif // __cplusplus
+
+/**
+ * A named set of password requirements derived from a published standard.
+ *
+ * Standards that forbid composition rules are encoded with a minimum count of `0`
+ * and no effective maximum count for decimal integers and special characters.
+ */
+enum MkPassPreset
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * NIST Special Publication 800-63B (revision 4), section 3.1.1.2.
+     *
+     * At least 15 characters and no composition rules.
+     */
+    MK_PASS_PRESET_NIST80063B,
+    /**
+     * PCI DSS (version 4.0.1), requirement 8.3.6.
+     *
+     * At least 12 characters, including numeric and alphabetic characters.
+     */
+    MK_PASS_PRESET_PCI_DSS,
+    /**
+     * CIS Critical Security Controls (version 8), safeguard 5.2.
+     *
+     * At least 14 characters (for accounts without multi-factor authentication).
+     */
+    MK_PASS_PRESET_CIS,
+    /**
+     * OWASP Application Security Verification Standard (version 4.0.3),
+     * requirements 2.1.1 and 2.1.9.
+     *
+     * At least 12 characters and no composition rules.
+     */
+    MK_PASS_PRESET_OWASP_ASVS,
+};
+#ifndef __cplusplus
+typedef uint8_t MkPassPreset;
+#endif // __cplusplus
+
+/**
+ * An extended alphabet of (non-ASCII) letters that can be used in a password.
+ *
+ * Alphabets are bit flags that can be combined with a bitwise OR.
+ * Each alphabet only includes precomposed letters,
+ * so passwords are always in Unicode Normalization Form C (NFC).
+ */
+enum MkPassAlphabet
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * The letters of the Latin-1 Supplement block (like `À`, `ç`, and `ß`).
+     */
+    MK_PASS_ALPHABET_LATIN1 = (1 << 0),
+    /**
+     * The letters of the modern Greek alphabet (like `Α`, `β`, and `ς`).
+     */
+    MK_PASS_ALPHABET_GREEK = (1 << 1),
+    /**
+     * The letters of the basic Russian Cyrillic alphabet (like `Ж`, `ё`, and `я`).
+     */
+    MK_PASS_ALPHABET_CYRILLIC = (1 << 2),
+};
+#ifndef __cplusplus
+typedef uint8_t MkPassAlphabet;
+#endif // __cplusplus
+
+/**
+ * The (opaque) sets of characters sampled when generating a password.
+ *
+ * Create the sets with `mk_pass_charsets_new()` and free them with `mk_pass_charsets_free()`.
+ * Functions that only read the sets treat a null pointer as the default character sets.
+ */
+typedef struct MkPassCharacterSets MkPassCharacterSets;
+
+/**
+ * A structure to describe the requirements of a password's contents.
+ */
+typedef struct {
+    /**
+     * The length of the password.
+     */
+    uint16_t length;
+    /**
+     * How many decimal integer characters should the password contain?
+     */
+    uint16_t decimal;
+    /**
+     * How many special characters should the password contain?
+     */
+    uint16_t specials;
+    /**
+     * Should the first character always be a letter?
+     */
+    bool first_is_letter;
+    /**
+     * Allow characters to be used more than once?
+     */
+    bool allow_repeats;
+    /**
+     * At least how many uppercase letters should the password contain?
+     */
+    uint16_t uppercase;
+    /**
+     * At least how many lowercase letters should the password contain?
+     */
+    uint16_t lowercase;
+    /**
+     * At most how many decimal integer characters should the password contain?
+     *
+     * If not `0`, the actual count is picked randomly from `decimal` to this value.
+     */
+    uint16_t max_decimal;
+    /**
+     * At most how many special characters should the password contain?
+     *
+     * If not `0`, the actual count is picked randomly from `specials` to this value.
+     */
+    uint16_t max_specials;
+    /**
+     * At most how many uppercase letters should the password contain?
+     *
+     * A value of `0` means there is no maximum.
+     */
+    uint16_t max_uppercase;
+    /**
+     * At most how many lowercase letters should the password contain?
+     *
+     * A value of `0` means there is no maximum.
+     */
+    uint16_t max_lowercase;
+} MkPassPasswordRequirements;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * The list of characters that are easily mistaken for one another when read by a person.
+ */
+extern const char MK_PASS_AMBIGUOUS_CHARACTERS[8];
+
+/**
+ * The list of possible decimal integers used when generating a password.
+ */
+extern const char MK_PASS_DECIMAL[11];
+
+/**
+ * The list of possible lowercase letters used when generating a password.
+ */
+extern const char MK_PASS_LOWERCASE[27];
+
+/**
+ * The list of possible special characters used when generating a password.
+ */
+extern const char MK_PASS_SPECIAL_CHARACTERS[17];
+
+/**
+ * The list of possible uppercase letters used when generating a password.
+ */
+extern const char MK_PASS_UPPERCASE[27];
+
+/**
+ * Add the letters of the given `alphabets` (a combination of `Alphabet` flags)
+ * to the sets of uppercase and lowercase letters.
+ *
+ * Returns `Status::NullPointer` if the `sets` are null.
+ */
+MkPassStatus mk_pass_charsets_add_alphabets(MkPassCharacterSets *sets,
+                                            uint8_t alphabets);
+
+/**
+ * Check the `config` against the given character `sets` without changing it.
+ *
+ * Returns null if `mk_pass_charsets_validate()` would not change the `config`.
+ * Otherwise, returns a newly allocated message that describes the first violated
+ * requirement. The returned message must be freed with `mk_pass_string_free()`.
+ */
+char *mk_pass_charsets_check(const MkPassCharacterSets *sets,
+                             MkPassPasswordRequirements config);
+
+/**
+ * Create a copy of the given character `sets`.
+ *
+ * The returned sets must be freed with `mk_pass_charsets_free()`.
+ */
+MkPassCharacterSets *mk_pass_charsets_clone(const MkPassCharacterSets *sets);
+
+/**
+ * Calculate the theoretical entropy (in bits) of a password generated
+ * from the given character `sets` with the given `config`.
+ *
+ * See also `mk_pass_password_entropy()`.
+ */
+double mk_pass_charsets_entropy(const MkPassCharacterSets *sets,
+                                MkPassPasswordRequirements config);
+
+/**
+ * Remove the given null-terminated UTF-8 `chars` from all sets.
+ *
+ * Returns `Status::InvalidUtf8` if the `chars` are not valid UTF-8,
+ * or `Status::NullPointer` if the `sets` or `chars` are null.
+ */
+MkPassStatus mk_pass_charsets_exclude(MkPassCharacterSets *sets,
+                                      const char *chars);
+
+/**
+ * Free the character `sets` created by `mk_pass_charsets_new()` or `mk_pass_charsets_clone()`.
+ *
+ * Passing null `sets` does nothing.
+ */
+void mk_pass_charsets_free(MkPassCharacterSets *sets);
+
+/**
+ * Generate a password from the given character `sets` given the constraints
+ * specified by `config`.
+ *
+ * This behaves like `mk_pass_generate_alloc()`, but the characters are
+ * sampled from the given `sets`. The returned password must be freed with
+ * `mk_pass_string_free()`.
+ *
+ * Returns null if the sets of uppercase and lowercase letters are both empty.
+ */
+char *mk_pass_charsets_generate_alloc(const MkPassCharacterSets *sets,
+                                      MkPassPasswordRequirements config);
+
+/**
+ * Generate a `count` of passwords from the given character `sets` given the
+ * constraints specified by `config`.
+ *
+ * If `unique` is `true`, then no two passwords in the batch are the same.
+ * In this case, the `count` is limited to the number of possible passwords
+ * that satisfy the `config`.
+ *
+ * Returns a newly allocated array of newly allocated, null-terminated passwords.
+ * If `written` is not null, then it is set to the number of passwords in the array.
+ * The returned array must be freed with `mk_pass_strings_free()`.
+ * Returns null if the sets of uppercase and lowercase letters are both empty.
+ */
+char **mk_pass_charsets_generate_many(const MkPassCharacterSets *sets,
+                                      MkPassPasswordRequirements config,
+                                      uint32_t count,
+                                      bool unique,
+                                      uint32_t *written);
+
+/**
+ * Get the set of the given `kind` of characters.
+ *
+ * The characters are written to the `buffer` as UTF-8 followed by a null terminator.
+ * If `required` is not null, then it is set to the number of bytes needed for
+ * the characters and the null terminator.
+ *
+ * Returns
+ *
+ * - `Status::Ok` if the characters were written to the `buffer`.
+ * - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+ * - `Status::NullPointer` if the `buffer` is null.
+ */
+MkPassStatus mk_pass_charsets_get(const MkPassCharacterSets *sets,
+                                  MkPassCharKind kind,
+                                  char *buffer,
+                                  size_t capacity,
+                                  size_t *required);
+
+/**
+ * Create the default character sets.
+ *
+ * The returned sets must be freed with `mk_pass_charsets_free()`.
+ */
+MkPassCharacterSets *mk_pass_charsets_new(void);
+
+/**
+ * Replace the set of the given `kind` of characters with the null-terminated UTF-8 `chars`.
+ *
+ * Each character is normalized to Unicode Normalization Form C (NFC).
+ * Characters that cannot stand alone in NFC (like combining marks) and
+ * duplicate characters are ignored.
+ *
+ * Returns `Status::InvalidUtf8` if the `chars` are not valid UTF-8,
+ * or `Status::NullPointer` if the `sets` or `chars` are null.
+ */
+MkPassStatus mk_pass_charsets_set(MkPassCharacterSets *sets,
+                                  MkPassCharKind kind,
+                                  const char *chars);
+
+/**
+ * Validates the `config` against the given character `sets`.
+ *
+ * This behaves like `mk_pass_validate_requirements()`, but the limits that
+ * avoid repetitions are derived from the sizes of the given `sets`.
+ */
+MkPassPasswordRequirements mk_pass_charsets_validate(const MkPassCharacterSets *sets,
+                                                     MkPassPasswordRequirements config);
+
+/**
+ * Get the default password requirements.
+ */
+MkPassPasswordRequirements mk_pass_default_requirements(void);
+
+/**
+ * Generate a password given the constraints specified by `config`.
+ *
+ * This function will invoke `mk_pass_validate_requirements(&config)` to
+ * ensure basic password requirements are met.
+ *
+ * The password is written to the `buffer` followed by a null terminator.
+ * If `required` is not null, then it is set to the number of bytes needed for
+ * the password and its null terminator (`mk_pass_validate_requirements(&config).length + 1`).
+ * Thus, the required size can be queried by passing a null `buffer` and a `capacity` of `0`.
+ *
+ * Returns
+ *
+ * - `Status::Ok` if the password was written to the `buffer`.
+ * - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+ * - `Status::NullPointer` if the `buffer` is null.
+ */
+MkPassStatus mk_pass_generate(char *buffer,
+                              size_t capacity,
+                              MkPassPasswordRequirements config,
+                              size_t *required);
+
+/**
+ * Generate a password given the constraints specified by `config`.
+ *
+ * This function will invoke `mk_pass_validate_requirements(&config)` to
+ * ensure basic password requirements are met.
+ *
+ * Returns a newly allocated, null-terminated password.
+ * The returned password must be freed with `mk_pass_string_free()`.
+ */
+char *mk_pass_generate_alloc(MkPassPasswordRequirements config);
+
+/**
+ * Generate a `count` of passwords given the constraints specified by `config`.
+ *
+ * This function will invoke `mk_pass_validate_requirements(&config)` once for the entire batch.
+ * Each password is written to the `buffer` followed by a null terminator.
+ * If `required` is not null, then it is set to the number of bytes needed for
+ * all passwords and their null terminators
+ * (`count * (mk_pass_validate_requirements(&config).length + 1)`).
+ *
+ * If `unique` is `true`, then no two passwords in the batch are the same.
+ * In this case, the `count` is limited to the number of possible passwords
+ * that satisfy the `config`.
+ * If `written` is not null, then it is set to the number of passwords written to the `buffer`.
+ *
+ * Returns
+ *
+ * - `Status::Ok` if the passwords were written to the `buffer`.
+ * - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+ * - `Status::NullPointer` if the `buffer` is null.
+ */
+MkPassStatus mk_pass_generate_passwords(char *buffer,
+                                        size_t capacity,
+                                        uint32_t count,
+                                        bool unique,
+                                        MkPassPasswordRequirements config,
+                                        uint32_t *written,
+                                        size_t *required);
+
+/**
+ * Generate a pronounceable password given the constraints specified by `config`.
+ *
+ * The letters alternate between consonant clusters (like `th`) and vowel clusters
+ * (like `ou`). The required decimal integers and special characters are inserted
+ * at random positions. The `config` is validated with `allowRepeats` enabled,
+ * so characters may be repeated even if `allowRepeats` is disabled.
+ * The password is written to the `buffer` followed by a null terminator.
+ * If `required` is not null, then it is set to the number of bytes needed for
+ * the password and its null terminator (which does not depend on the random password).
+ *
+ * Returns
+ *
+ * - `Status::Ok` if the password was written to the `buffer`.
+ * - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+ * - `Status::NullPointer` if the `buffer` is null.
+ */
+MkPassStatus mk_pass_generate_pronounceable(char *buffer,
+                                            size_t capacity,
+                                            MkPassPasswordRequirements config,
+                                            size_t *required);
+
+/**
+ * Generate a password that may contain the letters of the given `alphabets`.
+ *
+ * This behaves like `mk_pass_generate()`, but the letters of the `alphabets`
+ * (a combination of `Alphabet` flags) are added to the ASCII letters.
+ * The password is written to the `buffer` as UTF-8 followed by a null terminator.
+ * If `required` is not null, then it is set to the number of bytes needed for
+ * the longest possible password and its null terminator.
+ * Each character takes up to 4 bytes, so this is at most
+ * `mk_pass_validate_requirements(&config).length * 4 + 1` bytes.
+ *
+ * Returns
+ *
+ * - `Status::Ok` if the password was written to the `buffer`.
+ * - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+ * - `Status::NullPointer` if the `buffer` is null.
+ */
+MkPassStatus mk_pass_generate_unicode(char *buffer,
+                                      size_t capacity,
+                                      MkPassPasswordRequirements config,
+                                      uint8_t alphabets,
+                                      size_t *required);
+
+/**
+ * Calculate the theoretical entropy (in bits) of a password generated with the given `config`.
+ *
+ * This function will invoke `mk_pass_validate_requirements(&config)` to
+ * ensure basic password requirements are met.
+ *
+ * The entropy is the base 2 logarithm of the number of possible passwords
+ * that satisfy the `config`.
+ */
+double mk_pass_password_entropy(MkPassPasswordRequirements config);
+
+/**
+ * Get the password requirements of the given `preset`.
+ */
+MkPassPasswordRequirements mk_pass_preset_requirements(MkPassPreset preset);
+
+/**
+ * Calculate the entropy (in bits) of a pronounceable password generated with the given `config`.
+ *
+ * Pronounceable passwords have much less entropy than other passwords of the same length.
+ * See also `mk_pass_generate_pronounceable()`.
+ */
+double mk_pass_pronounceable_entropy(MkPassPasswordRequirements config);
+
+/**
+ * The function used as a native entrypoint for an executable.
+ */
+void mk_pass_run_main(void);
+
+/**
+ * Free a `string` allocated by this library (like `mk_pass_generate_alloc()`).
+ *
+ * The `string` is wiped from memory before it is freed.
+ * Passing a null `string` does nothing.
+ */
+void mk_pass_string_free(char *string);
+
+/**
+ * Free an array of `count` `strings` allocated by this library
+ * (like `mk_pass_charsets_generate_many()`).
+ *
+ * Each string is wiped from memory before it is freed.
+ * Passing null `strings` does nothing.
+ */
+void mk_pass_strings_free(char **strings, uint32_t count);
+
+/**
+ * Validates the instance's values.
+ *
+ * This returns a mutated copy of the instance where the values satisfy
+ * "sane minimum requirements" suitable for any password.
+ *
+ * The phrase "sane minimum requirements" implies
+ *
+ * 1. `length` is not less than 10
+ * 2. To avoid repetitions, `length` is not more than the 52 letters plus
+ *    the (maximum) counts of decimal integers and special characters. For example,
+ *
+ *    - 54 if the password contains 1 decimal integer and 1 special character (the default)
+ *    - 78 if the password contains 10 decimal integers and 16 special characters
+ *    - 65535 if repeated characters are allowed
+ * 3. `uppercase` and `lowercase` letter counts do not overrule the required number of
+ *
+ *    - lowercase letters (1; if `uppercase` is the overruling count)
+ *    - decimal integers (1; if `decimal` is specified as non-zero value)
+ *    - special characters (1; if `specials` is specified as non-zero value)
+ * 4. `specials` character count does not overrule the required number of
+ *
+ *    - letters (at least 2; 1 uppercase and 1 lowercase or
+ *      the specified `uppercase` and `lowercase` counts)
+ *    - decimal integers (if `decimal` is specified as non-zero value)
+ * 5. `decimal` character count does not overrule the required number of
+ *
+ *    - letters (at least 2; 1 uppercase and 1 lowercase or
+ *      the specified `uppercase` and `lowercase` counts)
+ *    - special characters (if `specials` is specified as non-zero value)
+ * 6. To avoid repetitions, `decimal` is not more than 10, `specials` is not more than 16,
+ *    and `uppercase` and `lowercase` are each not more than 26.
+ *    Consequently, `length` is shortened if there are not enough letters (52)
+ *    to fill the remainder.
+ * 7. Each maximum count (like `maxDecimal`) is not less than the respective minimum count.
+ *    Consequently, `length` is shortened if the maximum counts cannot fill it.
+ *
+ * # About resolving conflicts
+ *
+ * If this function finds a conflict between the specified number of
+ * `specials` characters and `decimal`, then decimal integers takes precedence.
+ * Likewise, `uppercase` letters take precedence over `lowercase` letters.
+ *
+ * For example:
+ *
+ * ```c
+ * #include <mk_pass.hpp>
+ *
+ * PasswordRequirements req = {
+ *     .length = 16,
+ *     .decimal = 16,
+ *     .specials = 16,
+ *     .firstIsLetter = true,
+ *     .allowRepeats = false,
+ * };
+ * PasswordRequirements expected = {
+ *     .length = 16,
+ *     .decimal = 10,
+ *     .specials = 1,
+ *     .firstIsLetter = true,
+ *     .allowRepeats = false,
+ * };
+ * assert(mk_pass_validate_requirements(&req) == expected);
+ * ```
+ */
+MkPassPasswordRequirements mk_pass_validate_requirements(const MkPassPasswordRequirements *config);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MK_PASS_C_H_ */
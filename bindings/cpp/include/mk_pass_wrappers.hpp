@@ -0,0 +1,593 @@
+#ifndef MK_PASS_WRAPPERS_H_
+#define MK_PASS_WRAPPERS_H_
+
+// The C++ API that wraps the (prefixed) functions declared in mk_pass.hpp.
+// Unlike mk_pass.hpp, this file is not generated by cbindgen.
+
+#include "mk_pass.hpp"
+#include <cstring>
+#include <memory>
+#include <ostream>
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+namespace mk_pass {
+
+/// Combine `Alphabet` flags.
+inline uint8_t operator|(Alphabet lhs, Alphabet rhs) {
+    return static_cast<uint8_t>(lhs) | static_cast<uint8_t>(rhs);
+}
+
+/// Combine `Alphabet` flags.
+inline uint8_t operator|(uint8_t lhs, Alphabet rhs) {
+    return lhs | static_cast<uint8_t>(rhs);
+}
+
+/// The list of possible special characters used when generating a password.
+constexpr const char (&SPECIAL_CHARACTERS)[17] = MK_PASS_SPECIAL_CHARACTERS;
+
+/// The list of possible decimal integers used when generating a password.
+constexpr const char (&DECIMAL)[11] = MK_PASS_DECIMAL;
+
+/// The list of possible uppercase letters used when generating a password.
+constexpr const char (&UPPERCASE)[27] = MK_PASS_UPPERCASE;
+
+/// The list of possible lowercase letters used when generating a password.
+constexpr const char (&LOWERCASE)[27] = MK_PASS_LOWERCASE;
+
+/// The list of characters that are easily mistaken for one another when read by
+/// a person.
+constexpr const char (&AMBIGUOUS_CHARACTERS)[8] = MK_PASS_AMBIGUOUS_CHARACTERS;
+
+/// Validates the instance's values.
+///
+/// See `mk_pass_validate_requirements()`.
+inline PasswordRequirements
+validateRequirements(const PasswordRequirements* config) {
+    return mk_pass_validate_requirements(config);
+}
+
+/// Get the password requirements of the given `preset`.
+inline PasswordRequirements presetRequirements(Preset preset) {
+    return mk_pass_preset_requirements(preset);
+}
+
+/// Generate a password that may contain the letters of the given `alphabets`.
+///
+/// See `mk_pass_generate_unicode()`.
+inline Status generateUnicodePassword(char* buffer, size_t capacity,
+                                      PasswordRequirements config,
+                                      uint8_t alphabets, size_t* required) {
+    return mk_pass_generate_unicode(buffer, capacity, config, alphabets,
+                                    required);
+}
+
+/// Generate a `count` of passwords given the constraints specified by `config`.
+///
+/// See `mk_pass_generate_passwords()`.
+inline Status generatePasswords(char* buffer, size_t capacity, uint32_t count,
+                                bool unique, PasswordRequirements config,
+                                uint32_t* written, size_t* required) {
+    return mk_pass_generate_passwords(buffer, capacity, count, unique, config,
+                                      written, required);
+}
+
+/// Calculate the theoretical entropy (in bits) of a password generated with the
+/// given `config`.
+///
+/// See `mk_pass_password_entropy()`.
+inline double passwordEntropy(PasswordRequirements config) {
+    return mk_pass_password_entropy(config);
+}
+
+/// Generate a pronounceable password given the constraints specified by
+/// `config`.
+///
+/// See `mk_pass_generate_pronounceable()`.
+inline Status generatePronounceablePassword(char* buffer, size_t capacity,
+                                            PasswordRequirements config,
+                                            size_t* required) {
+    return mk_pass_generate_pronounceable(buffer, capacity, config, required);
+}
+
+/// Calculate the entropy (in bits) of a pronounceable password generated with
+/// the given `config`.
+///
+/// See `mk_pass_pronounceable_entropy()`.
+inline double pronounceableEntropy(PasswordRequirements config) {
+    return mk_pass_pronounceable_entropy(config);
+}
+
+/// The function used as a native entrypoint for an executable.
+inline void runMain() { mk_pass_run_main(); }
+
+/// Overwrite the `size` bytes at `data` with zeros.
+///
+/// The bytes are written through a volatile pointer,
+/// so the compiler cannot optimize the writes away.
+inline void secureWipe(void* data, size_t size) {
+    volatile unsigned char* bytes = static_cast<volatile unsigned char*>(data);
+    while (size--) {
+        *bytes++ = 0;
+    }
+}
+
+/// A secret string (like a generated password) that is wiped from memory when
+/// destroyed.
+///
+/// This class cannot be copied, and writing it to a stream does not reveal the
+/// secret. Use `exposeSecret()` to access the secret.
+class SecretString {
+  public:
+    /// Copy the `size` bytes at `data` into a new secret.
+    SecretString(const char* data, size_t size) : value(data, size) {}
+
+    /// Take the secret from `other`, and wipe the `other` secret.
+    SecretString(SecretString&& other) : value(other.value) { other.wipe(); }
+
+    /// Take the secret from `other`, and wipe both the old and the `other`
+    /// secret.
+    SecretString& operator=(SecretString&& other) {
+        if (this != &other) {
+            wipe();
+            value = other.value;
+            other.wipe();
+        }
+        return *this;
+    }
+
+    SecretString(const SecretString&) = delete;
+    SecretString& operator=(const SecretString&) = delete;
+
+    ~SecretString() { wipe(); }
+
+    /// Get the secret.
+    ///
+    /// Any copy made from the returned value is not wiped.
+    const std::string& exposeSecret() const { return value; }
+
+    /// Write a redacted representation (not the secret) to the `stream`.
+    friend std::ostream& operator<<(std::ostream& stream, const SecretString&) {
+        return stream << "[REDACTED]";
+    }
+
+  private:
+    std::string value;
+
+    /// Overwrite the entire allocation of the secret with zeros.
+    void wipe() {
+        value.resize(value.capacity());
+        secureWipe(&value[0], value.size());
+        value.clear();
+    }
+};
+
+/// Frees a string allocated by this library with `mk_pass_string_free()`.
+struct StringDeleter {
+    void operator()(char* string) const { mk_pass_string_free(string); }
+};
+
+/// A string allocated by this library (like a password from
+/// `mk_pass_generate_alloc()`).
+///
+/// The string is wiped from memory and freed when destroyed.
+using UniqueString = std::unique_ptr<char, StringDeleter>;
+
+/// Generate a password given the constraints specified by `config`.
+///
+/// This is a convenience wrapper around `mk_pass_generate_alloc()`
+/// that manages the allocated password.
+inline std::string generatePassword(PasswordRequirements config) {
+    UniqueString password(mk_pass_generate_alloc(config));
+    return password ? std::string(password.get()) : std::string();
+}
+
+/// Generate a `count` of passwords given the constraints specified by `config`.
+///
+/// This is a convenience wrapper around `mk_pass_generate_passwords()`
+/// that manages the buffer of passwords.
+inline std::vector<std::string> generatePasswords(PasswordRequirements config,
+                                                  uint32_t count,
+                                                  bool unique = false) {
+    PasswordRequirements validated = validateRequirements(&config);
+    size_t stride = validated.length + 1;
+    std::vector<char> buffer(stride * count, 0);
+    uint32_t written = 0;
+    generatePasswords(buffer.data(), buffer.size(), count, unique, config,
+                      &written, nullptr);
+    std::vector<std::string> passwords;
+    passwords.reserve(written);
+    for (uint32_t i = 0; i < written; ++i) {
+        passwords.emplace_back(buffer.data() + (i * stride), validated.length);
+    }
+    secureWipe(buffer.data(), buffer.size());
+    return passwords;
+}
+
+/// Generate a password that may contain the letters of the given `alphabets`.
+///
+/// This is a convenience wrapper around `mk_pass_generate_unicode()`
+/// that manages the buffer of UTF-8 bytes.
+/// Note, the `std::string::size()` is the number of bytes, not characters.
+inline std::string generatePassword(PasswordRequirements config,
+                                    uint8_t alphabets) {
+    size_t required = 0;
+    generateUnicodePassword(nullptr, 0, config, alphabets, &required);
+    std::vector<char> buffer(required, 0);
+    generateUnicodePassword(buffer.data(), buffer.size(), config, alphabets,
+                            nullptr);
+    std::string password(buffer.data());
+    secureWipe(buffer.data(), buffer.size());
+    return password;
+}
+
+/// Generate a password that may contain the letters of the given `alphabet`.
+///
+/// See also `generateUnicodePassword()`.
+inline std::string generatePassword(PasswordRequirements config,
+                                    Alphabet alphabet) {
+    return generatePassword(config, static_cast<uint8_t>(alphabet));
+}
+
+/// Generate a pronounceable password given the constraints specified by
+/// `config`.
+///
+/// This is a convenience wrapper around `mk_pass_generate_pronounceable()`
+/// that manages the buffer.
+inline std::string generatePronounceablePassword(PasswordRequirements config) {
+    size_t required = 0;
+    generatePronounceablePassword(nullptr, 0, config, &required);
+    std::vector<char> buffer(required, 0);
+    generatePronounceablePassword(buffer.data(), buffer.size(), config,
+                                  nullptr);
+    std::string password(buffer.data());
+    secureWipe(buffer.data(), buffer.size());
+    return password;
+}
+
+/// Generate a password that is wiped from memory when destroyed.
+///
+/// This behaves like `generatePassword()`, but the password is returned as a
+/// `SecretString`, and the intermediate buffer is wiped.
+/// The letters of the given `alphabets` (a combination of `Alphabet` flags)
+/// are added to the ASCII letters.
+inline SecretString generateSecretPassword(PasswordRequirements config,
+                                           uint8_t alphabets = 0) {
+    size_t required = 0;
+    generateUnicodePassword(nullptr, 0, config, alphabets, &required);
+    std::vector<char> buffer(required, 0);
+    generateUnicodePassword(buffer.data(), buffer.size(), config, alphabets,
+                            nullptr);
+    SecretString password(buffer.data(), strlen(buffer.data()));
+    secureWipe(buffer.data(), buffer.size());
+    return password;
+}
+
+/// An error reported by this library.
+class Error : public std::runtime_error {
+  public:
+    using std::runtime_error::runtime_error;
+};
+
+/// The password requirements do not satisfy basic password requirements.
+///
+/// See `Generator::check()`.
+class RequirementsError : public Error {
+  public:
+    using Error::Error;
+};
+
+/// A password generator with builder-style setters.
+///
+/// The generator holds the password requirements and the sets of characters
+/// sampled when generating a password. Each setter returns the generator,
+/// so the setters can be chained:
+///
+/// ```cpp
+/// #include <mk_pass.hpp>
+///
+/// std::string password = mk_pass::Generator()
+///                            .length(20)
+///                            .specials(2)
+///                            .withSpecial("-_.")
+///                            .generate();
+/// ```
+///
+/// Errors are reported by throwing an `Error` (or a `RequirementsError`).
+class Generator {
+  public:
+    /// Create a generator with the default requirements and character sets.
+    Generator() : Generator(mk_pass_default_requirements()) {}
+
+    /// Create a generator with the given requirements and the default character
+    /// sets.
+    explicit Generator(PasswordRequirements config)
+        : config(config), sets(mk_pass_charsets_new()) {}
+
+    /// Create a generator with the requirements of the given `preset`.
+    explicit Generator(Preset preset) : Generator(presetRequirements(preset)) {}
+
+    /// Copy the requirements and character sets of `other`.
+    Generator(const Generator& other)
+        : config(other.config), strictMode(other.strictMode),
+          sets(mk_pass_charsets_clone(other.sets.get())) {}
+
+    /// Copy the requirements and character sets of `other`.
+    Generator& operator=(const Generator& other) {
+        if (this != &other) {
+            config = other.config;
+            strictMode = other.strictMode;
+            sets.reset(mk_pass_charsets_clone(other.sets.get()));
+        }
+        return *this;
+    }
+
+    Generator(Generator&&) noexcept = default;
+    Generator& operator=(Generator&&) noexcept = default;
+
+    /// Set how many characters the password should contain.
+    Generator& length(uint16_t value) {
+        config.length = value;
+        return *this;
+    }
+
+    /// Set how many decimal integer characters the password should contain.
+    Generator& decimal(uint16_t value) {
+        config.decimal = value;
+        return *this;
+    }
+
+    /// Set how many special characters the password should contain.
+    Generator& specials(uint16_t value) {
+        config.specials = value;
+        return *this;
+    }
+
+    /// Set at least how many uppercase letters the password should contain.
+    Generator& uppercase(uint16_t value) {
+        config.uppercase = value;
+        return *this;
+    }
+
+    /// Set at least how many lowercase letters the password should contain.
+    Generator& lowercase(uint16_t value) {
+        config.lowercase = value;
+        return *this;
+    }
+
+    /// Set at most how many decimal integer characters the password should
+    /// contain.
+    ///
+    /// A value of `0` means the count is exactly `decimal`.
+    Generator& maxDecimal(uint16_t value) {
+        config.maxDecimal = value;
+        return *this;
+    }
+
+    /// Set at most how many special characters the password should contain.
+    ///
+    /// A value of `0` means the count is exactly `specials`.
+    Generator& maxSpecials(uint16_t value) {
+        config.maxSpecials = value;
+        return *this;
+    }
+
+    /// Set at most how many uppercase letters the password should contain.
+    ///
+    /// A value of `0` means there is no maximum.
+    Generator& maxUppercase(uint16_t value) {
+        config.maxUppercase = value;
+        return *this;
+    }
+
+    /// Set at most how many lowercase letters the password should contain.
+    ///
+    /// A value of `0` means there is no maximum.
+    Generator& maxLowercase(uint16_t value) {
+        config.maxLowercase = value;
+        return *this;
+    }
+
+    /// Set whether the first character should always be a letter.
+    Generator& firstIsLetter(bool value = true) {
+        config.firstIsLetter = value;
+        return *this;
+    }
+
+    /// Set whether characters may be used more than once.
+    Generator& allowRepeats(bool value = true) {
+        config.allowRepeats = value;
+        return *this;
+    }
+
+    /// Replace all password requirements.
+    Generator& requirements(PasswordRequirements value) {
+        config = value;
+        return *this;
+    }
+
+    /// Set whether the requirements are checked (instead of validated) when
+    /// generating.
+    ///
+    /// In strict mode, generating a password throws a `RequirementsError`
+    /// if `check()` fails (instead of silently adjusting the requirements).
+    Generator& strict(bool value = true) {
+        strictMode = value;
+        return *this;
+    }
+
+    /// Replace the set of uppercase letters with the given UTF-8 `chars`.
+    Generator& withUppercase(const std::string& chars) {
+        return withCharacters(CharKind::Uppercase, chars);
+    }
+
+    /// Replace the set of lowercase letters with the given UTF-8 `chars`.
+    Generator& withLowercase(const std::string& chars) {
+        return withCharacters(CharKind::Lowercase, chars);
+    }
+
+    /// Replace the set of decimal integers with the given UTF-8 `chars`.
+    Generator& withDecimal(const std::string& chars) {
+        return withCharacters(CharKind::Decimal, chars);
+    }
+
+    /// Replace the set of special characters with the given UTF-8 `chars`.
+    Generator& withSpecial(const std::string& chars) {
+        return withCharacters(CharKind::Special, chars);
+    }
+
+    /// Replace the set of the given `kind` of characters with the given UTF-8
+    /// `chars`.
+    ///
+    /// See also `mk_pass_charsets_set()`.
+    Generator& withCharacters(CharKind kind, const std::string& chars) {
+        throwIfFailed(mk_pass_charsets_set(sets.get(), kind, chars.c_str()));
+        return *this;
+    }
+
+    /// Add the letters of the given `alphabets` (a combination of `Alphabet`
+    /// flags) to the sets of uppercase and lowercase letters.
+    Generator& withAlphabets(uint8_t alphabets) {
+        throwIfFailed(mk_pass_charsets_add_alphabets(sets.get(), alphabets));
+        return *this;
+    }
+
+    /// Add the letters of the given `alphabet` to the sets of uppercase and
+    /// lowercase letters.
+    Generator& withAlphabet(Alphabet alphabet) {
+        return withAlphabets(static_cast<uint8_t>(alphabet));
+    }
+
+    /// Remove the given UTF-8 `chars` from all sets.
+    Generator& exclude(const std::string& chars) {
+        throwIfFailed(mk_pass_charsets_exclude(sets.get(), chars.c_str()));
+        return *this;
+    }
+
+    /// Remove the `AMBIGUOUS_CHARACTERS` from all sets.
+    Generator& withoutAmbiguous() { return exclude(AMBIGUOUS_CHARACTERS); }
+
+    /// Get the password requirements (as given).
+    const PasswordRequirements& requirements() const { return config; }
+
+    /// Get the password requirements validated against the character sets.
+    ///
+    /// See also `mk_pass_charsets_validate()`.
+    PasswordRequirements validated() const {
+        return mk_pass_charsets_validate(sets.get(), config);
+    }
+
+    /// Throw a `RequirementsError` if the password requirements do not
+    /// satisfy basic password requirements.
+    ///
+    /// See also `mk_pass_charsets_check()`.
+    void check() const {
+        UniqueString message(mk_pass_charsets_check(sets.get(), config));
+        if (message) {
+            throw RequirementsError(message.get());
+        }
+    }
+
+    /// Get the set of the given `kind` of characters as a UTF-8 string.
+    std::string characters(CharKind kind) const {
+        size_t required = 0;
+        mk_pass_charsets_get(sets.get(), kind, nullptr, 0, &required);
+        std::string chars(required, '\0');
+        throwIfFailed(mk_pass_charsets_get(sets.get(), kind, &chars[0],
+                                           chars.size(), nullptr));
+        chars.resize(required - 1);
+        return chars;
+    }
+
+    /// Calculate the theoretical entropy (in bits) of a generated password.
+    double entropy() const {
+        return mk_pass_charsets_entropy(sets.get(), config);
+    }
+
+    /// Generate a password.
+    ///
+    /// Note, the `std::string::size()` is the number of bytes, not characters.
+    std::string generate() const {
+        UniqueString password = generateUnique();
+        return std::string(password.get());
+    }
+
+    /// Generate a password that is wiped from memory when destroyed.
+    SecretString generateSecret() const {
+        UniqueString password = generateUnique();
+        return SecretString(password.get(), strlen(password.get()));
+    }
+
+    /// Generate a `count` of passwords.
+    ///
+    /// If `unique` is `true`, then no two passwords are the same.
+    /// In this case, the `count` is limited to the number of possible
+    /// passwords.
+    std::vector<std::string> generate(uint32_t count,
+                                      bool unique = false) const {
+        if (strictMode) {
+            check();
+        }
+        uint32_t written = 0;
+        char** strings = mk_pass_charsets_generate_many(
+            sets.get(), config, count, unique, &written);
+        if (strings == nullptr) {
+            throw Error("Both sets of letters are empty");
+        }
+        StringsDeleter deleter{written};
+        std::unique_ptr<char*, StringsDeleter> managed(strings, deleter);
+        return std::vector<std::string>(strings, strings + written);
+    }
+
+  private:
+    /// Frees the character sets with `mk_pass_charsets_free()`.
+    struct CharacterSetsDeleter {
+        void operator()(CharacterSets* sets) const {
+            mk_pass_charsets_free(sets);
+        }
+    };
+
+    /// Frees an array of strings with `mk_pass_strings_free()`.
+    struct StringsDeleter {
+        uint32_t count;
+        void operator()(char** strings) const {
+            mk_pass_strings_free(strings, count);
+        }
+    };
+
+    PasswordRequirements config;
+    bool strictMode = false;
+    std::unique_ptr<CharacterSets, CharacterSetsDeleter> sets;
+
+    /// Throw an `Error` describing the given `status` (if not `Status::Ok`).
+    static void throwIfFailed(Status status) {
+        switch (status) {
+        case Status::Ok:
+            return;
+        case Status::NullPointer:
+            throw Error("The character sets are not available");
+        case Status::BufferTooSmall:
+            throw Error("The buffer is too small");
+        case Status::InvalidUtf8:
+            throw Error("The characters are not valid UTF-8");
+        }
+    }
+
+    /// Generate a password that is freed (and wiped) when destroyed.
+    UniqueString generateUnique() const {
+        if (strictMode) {
+            check();
+        }
+        char* raw = mk_pass_charsets_generate_alloc(sets.get(), config);
+        UniqueString password(raw);
+        if (!password) {
+            throw Error("Both sets of letters are empty");
+        }
+        return password;
+    }
+};
+
+} // namespace mk_pass
+
+#endif // MK_PASS_WRAPPERS_H_
@@ -1,17 +1,49 @@
 #![allow(clippy::missing_safety_doc)]
-use std::ffi::{CStr, CString, c_char};
+use std::{
+    borrow::Cow,
+    ffi::{CStr, CString, c_char},
+};
 
-// /// The list of possible special characters used when generating a password.
-// pub const SPECIAL_CHARACTERS: [char; 16] = ::mk_pass::SPECIAL_CHARACTERS;
+use zeroize::Zeroize;
 
-// /// The list of possible decimal used when generating a password.
-// pub const DECIMAL: [char; 10] = ::mk_pass::DECIMAL;
+/// Convert the given ASCII `chars` into a null-terminated C string of `M` (`N + 1`) bytes.
+const fn c_str<const N: usize, const M: usize>(chars: [char; N]) -> [c_char; M] {
+    assert!(
+        M == N + 1,
+        "The C string must have room for a null terminator"
+    );
+    let mut c_str = [0; M];
+    let mut i = 0;
+    while i < N {
+        assert!(
+            chars[i].is_ascii(),
+            "The C string can only contain ASCII characters"
+        );
+        c_str[i] = chars[i] as c_char;
+        i += 1;
+    }
+    c_str
+}
+
+/// The list of possible special characters used when generating a password.
+#[unsafe(no_mangle)]
+pub static MK_PASS_SPECIAL_CHARACTERS: [c_char; 17] = c_str(::mk_pass::SPECIAL_CHARACTERS);
+
+/// The list of possible decimal integers used when generating a password.
+#[unsafe(no_mangle)]
+pub static MK_PASS_DECIMAL: [c_char; 11] = c_str(::mk_pass::DECIMAL);
+
+/// The list of possible uppercase letters used when generating a password.
+#[unsafe(no_mangle)]
+pub static MK_PASS_UPPERCASE: [c_char; 27] = c_str(::mk_pass::UPPERCASE);
 
-// /// The list of possible uppercase letters used when generating a password.
-// pub const UPPERCASE: [char; 26] = ::mk_pass::UPPERCASE;
+/// The list of possible lowercase letters used when generating a password.
+#[unsafe(no_mangle)]
+pub static MK_PASS_LOWERCASE: [c_char; 27] = c_str(::mk_pass::LOWERCASE);
 
-// /// The list of possible lowercase letters used when generating a password.
-// pub const LOWERCASE: [char; 26] = ::mk_pass::LOWERCASE;
+/// The list of characters that are easily mistaken for one another when read by a person.
+#[unsafe(no_mangle)]
+pub static MK_PASS_AMBIGUOUS_CHARACTERS: [c_char; 8] = c_str(::mk_pass::AMBIGUOUS_CHARACTERS);
 
 /// A structure to describe the requirements of a password's contents.
 #[repr(C)]
@@ -30,6 +62,37 @@ pub struct PasswordRequirements {
 
     /// Allow characters to be used more than once?
     pub allow_repeats: bool,
+
+    /// At least how many uppercase letters should the password contain?
+    pub uppercase: u16,
+
+    /// At least how many lowercase letters should the password contain?
+    pub lowercase: u16,
+
+    /// At most how many decimal integer characters should the password contain?
+    ///
+    /// If not `0`, the actual count is picked randomly from `decimal` to this value.
+    pub max_decimal: u16,
+
+    /// At most how many special characters should the password contain?
+    ///
+    /// If not `0`, the actual count is picked randomly from `specials` to this value.
+    pub max_specials: u16,
+
+    /// At most how many uppercase letters should the password contain?
+    ///
+    /// A value of `0` means there is no maximum.
+    pub max_uppercase: u16,
+
+    /// At most how many lowercase letters should the password contain?
+    ///
+    /// A value of `0` means there is no maximum.
+    pub max_lowercase: u16,
+}
+
+/// Convert a maximum count to an [`Option`], where `0` means no maximum.
+fn optional_max(value: u16) -> Option<u16> {
+    (value > 0).then_some(value)
 }
 
 impl From<&PasswordRequirements> for ::mk_pass::PasswordRequirements {
@@ -40,6 +103,12 @@ impl From<&PasswordRequirements> for ::mk_pass::PasswordRequirements {
             specials: value.specials,
             first_is_letter: value.first_is_letter,
             allow_repeats: value.allow_repeats,
+            uppercase: value.uppercase,
+            lowercase: value.lowercase,
+            max_decimal: optional_max(value.max_decimal),
+            max_specials: optional_max(value.max_specials),
+            max_uppercase: optional_max(value.max_uppercase),
+            max_lowercase: optional_max(value.max_lowercase),
         }
     }
 }
@@ -52,6 +121,12 @@ impl From<PasswordRequirements> for ::mk_pass::PasswordRequirements {
             specials: value.specials,
             first_is_letter: value.first_is_letter,
             allow_repeats: value.allow_repeats,
+            uppercase: value.uppercase,
+            lowercase: value.lowercase,
+            max_decimal: optional_max(value.max_decimal),
+            max_specials: optional_max(value.max_specials),
+            max_uppercase: optional_max(value.max_uppercase),
+            max_lowercase: optional_max(value.max_lowercase),
         }
     }
 }
@@ -64,6 +139,12 @@ impl From<::mk_pass::PasswordRequirements> for PasswordRequirements {
             specials: value.specials,
             first_is_letter: value.first_is_letter,
             allow_repeats: value.allow_repeats,
+            uppercase: value.uppercase,
+            lowercase: value.lowercase,
+            max_decimal: value.max_decimal.unwrap_or_default(),
+            max_specials: value.max_specials.unwrap_or_default(),
+            max_uppercase: value.max_uppercase.unwrap_or_default(),
+            max_lowercase: value.max_lowercase.unwrap_or_default(),
         }
     }
 }
@@ -76,26 +157,39 @@ impl From<::mk_pass::PasswordRequirements> for PasswordRequirements {
 /// The phrase "sane minimum requirements" implies
 ///
 /// 1. `length` is not less than 10
-/// 2. To avoid repetitions, `length` is not more than
+/// 2. To avoid repetitions, `length` is not more than the 52 letters plus
+///    the (maximum) counts of decimal integers and special characters. For example,
 ///
-///    - 52 if only letters (no decimal integers or special characters) are used
-///    - 62 if only letters and decimal integers are used
-///    - 68 if only letters and special characters are used
-///    - 78 if letters, decimal integers, and special characters are used
+///    - 54 if the password contains 1 decimal integer and 1 special character (the default)
+///    - 78 if the password contains 10 decimal integers and 16 special characters
 ///    - 65535 if repeated characters are allowed
-/// 3. `specials` character count does not overrule the required number of
+/// 3. `uppercase` and `lowercase` letter counts do not overrule the required number of
 ///
-///    - letters (2; 1 uppercase and 1 lowercase)
+///    - lowercase letters (1; if `uppercase` is the overruling count)
+///    - decimal integers (1; if `decimal` is specified as non-zero value)
+///    - special characters (1; if `specials` is specified as non-zero value)
+/// 4. `specials` character count does not overrule the required number of
+///
+///    - letters (at least 2; 1 uppercase and 1 lowercase or
+///      the specified `uppercase` and `lowercase` counts)
 ///    - decimal integers (if `decimal` is specified as non-zero value)
-/// 4. `decimal` character count does not overrule the required number of
+/// 5. `decimal` character count does not overrule the required number of
 ///
-///    - letters (2; 1 uppercase and 1 lowercase)
+///    - letters (at least 2; 1 uppercase and 1 lowercase or
+///      the specified `uppercase` and `lowercase` counts)
 ///    - special characters (if `specials` is specified as non-zero value)
+/// 6. To avoid repetitions, `decimal` is not more than 10, `specials` is not more than 16,
+///    and `uppercase` and `lowercase` are each not more than 26.
+///    Consequently, `length` is shortened if there are not enough letters (52)
+///    to fill the remainder.
+/// 7. Each maximum count (like `maxDecimal`) is not less than the respective minimum count.
+///    Consequently, `length` is shortened if the maximum counts cannot fill it.
 ///
 /// # About resolving conflicts
 ///
 /// If this function finds a conflict between the specified number of
 /// `specials` characters and `decimal`, then decimal integers takes precedence.
+/// Likewise, `uppercase` letters take precedence over `lowercase` letters.
 ///
 /// For example:
 ///
@@ -103,67 +197,687 @@ impl From<::mk_pass::PasswordRequirements> for PasswordRequirements {
 /// #include <mk_pass.hpp>
 ///
 /// PasswordRequirements req = {
-///     16,    // length
-///     16,    // specials
-///     16,    // decimal
-///     true,  // firstIsLetter
-///     false, // allowRepeats
+///     .length = 16,
+///     .decimal = 16,
+///     .specials = 16,
+///     .firstIsLetter = true,
+///     .allowRepeats = false,
 /// };
 /// PasswordRequirements expected = {
-///     16,    // length
-///     1,     // specials
-///     13,    // decimal
-///     true,  // firstIsLetter
-///     false, // allowRepeats
+///     .length = 16,
+///     .decimal = 10,
+///     .specials = 1,
+///     .firstIsLetter = true,
+///     .allowRepeats = false,
 /// };
-/// assert(validateRequirements(&req) == expected);
+/// assert(mk_pass_validate_requirements(&req) == expected);
 /// ```
 #[unsafe(no_mangle)]
-pub extern "C" fn validateRequirements(config: &PasswordRequirements) -> PasswordRequirements {
+pub extern "C" fn mk_pass_validate_requirements(
+    config: &PasswordRequirements,
+) -> PasswordRequirements {
     ::mk_pass::PasswordRequirements::from(config)
         .validate()
         .into()
 }
 
+/// A named set of password requirements derived from a published standard.
+///
+/// Standards that forbid composition rules are encoded with a minimum count of `0`
+/// and no effective maximum count for decimal integers and special characters.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// NIST Special Publication 800-63B (revision 4), section 3.1.1.2.
+    ///
+    /// At least 15 characters and no composition rules.
+    Nist80063b,
+
+    /// PCI DSS (version 4.0.1), requirement 8.3.6.
+    ///
+    /// At least 12 characters, including numeric and alphabetic characters.
+    PciDss,
+
+    /// CIS Critical Security Controls (version 8), safeguard 5.2.
+    ///
+    /// At least 14 characters (for accounts without multi-factor authentication).
+    Cis,
+
+    /// OWASP Application Security Verification Standard (version 4.0.3),
+    /// requirements 2.1.1 and 2.1.9.
+    ///
+    /// At least 12 characters and no composition rules.
+    OwaspAsvs,
+}
+
+impl From<Preset> for ::mk_pass::Preset {
+    fn from(value: Preset) -> Self {
+        match value {
+            Preset::Nist80063b => Self::Nist80063b,
+            Preset::PciDss => Self::PciDss,
+            Preset::Cis => Self::Cis,
+            Preset::OwaspAsvs => Self::OwaspAsvs,
+        }
+    }
+}
+
+/// Get the default password requirements.
+#[unsafe(no_mangle)]
+pub extern "C" fn mk_pass_default_requirements() -> PasswordRequirements {
+    ::mk_pass::PasswordRequirements::default().into()
+}
+
+/// Get the password requirements of the given `preset`.
+#[unsafe(no_mangle)]
+pub extern "C" fn mk_pass_preset_requirements(preset: Preset) -> PasswordRequirements {
+    ::mk_pass::PasswordRequirements::preset(preset.into()).into()
+}
+
+/// The status returned by a function that writes to a given buffer.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The function succeeded.
+    Ok,
+
+    /// A given pointer was null.
+    NullPointer,
+
+    /// The given buffer's capacity is less than the required size.
+    ///
+    /// Nothing was written to the buffer.
+    BufferTooSmall,
+
+    /// A given string is not valid UTF-8.
+    InvalidUtf8,
+}
+
 /// Generate a password given the constraints specified by `config`.
 ///
-/// This function will invoke `validateRequirements(config)` to
+/// This function will invoke `mk_pass_validate_requirements(&config)` to
 /// ensure basic password requirements are met.
+///
+/// The password is written to the `buffer` followed by a null terminator.
+/// If `required` is not null, then it is set to the number of bytes needed for
+/// the password and its null terminator (`mk_pass_validate_requirements(&config).length + 1`).
+/// Thus, the required size can be queried by passing a null `buffer` and a `capacity` of `0`.
+///
+/// Returns
+///
+/// - `Status::Ok` if the password was written to the `buffer`.
+/// - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+/// - `Status::NullPointer` if the `buffer` is null.
 #[unsafe(no_mangle)]
-pub unsafe extern "C" fn generatePassword(
-    string: *mut c_char,
+pub unsafe extern "C" fn mk_pass_generate(
+    buffer: *mut c_char,
+    capacity: usize,
     config: PasswordRequirements,
-) -> u16 {
-    if string.is_null() {
-        eprintln!("The given pointer to c_str buffer was null");
-        return 0;
-    }
-    let c_string = unsafe { CStr::from_ptr(string) };
-    if c_string.is_empty() {
-        eprintln!("Given c_str buffer was zero sized");
-        return 0;
-    }
-    let real_config: ::mk_pass::PasswordRequirements = config.into();
-    let new_password = ::mk_pass::generate_password(real_config);
-    let result = CString::new(new_password.clone());
-    match result {
-        Ok(new_string) => {
-            unsafe { std::ptr::copy(new_string.as_ptr(), string, real_config.length as usize) };
-            real_config.length
+    required: *mut usize,
+) -> Status {
+    let real_config = ::mk_pass::PasswordRequirements::from(config).validate();
+    // the default character sets only contain ASCII characters (1 byte each)
+    let size = real_config.length as usize + 1;
+    let status = unsafe { check_buffer(buffer, capacity, size, required) };
+    if status == Status::Ok {
+        let new_password = ::mk_pass::generate_secret_password(real_config);
+        unsafe { write_c_str(new_password.expose_secret(), buffer) };
+    }
+    status
+}
+
+/// Generate a password given the constraints specified by `config`.
+///
+/// This function will invoke `mk_pass_validate_requirements(&config)` to
+/// ensure basic password requirements are met.
+///
+/// Returns a newly allocated, null-terminated password.
+/// The returned password must be freed with `mk_pass_string_free()`.
+#[unsafe(no_mangle)]
+pub extern "C" fn mk_pass_generate_alloc(config: PasswordRequirements) -> *mut c_char {
+    into_c_string(&::mk_pass::generate_secret_password(config.into()))
+}
+
+/// Copy the given `secret` into a newly allocated, null-terminated string.
+///
+/// Returns null if the `secret` contains a null character.
+fn into_c_string(secret: &::mk_pass::SecretString) -> *mut c_char {
+    let secret = secret.expose_secret();
+    // reserve the null terminator, so the bytes are not reallocated (leaving an unwiped copy)
+    let mut bytes = Vec::with_capacity(secret.len() + 1);
+    bytes.extend_from_slice(secret.as_bytes());
+    CString::new(bytes).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Free a `string` allocated by this library (like `mk_pass_generate_alloc()`).
+///
+/// The `string` is wiped from memory before it is freed.
+/// Passing a null `string` does nothing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        unsafe { CString::from_raw(string) }
+            .into_bytes_with_nul()
+            .zeroize();
+    }
+}
+
+/// Set the `required` size (if not null), then check that the `buffer` is not null
+/// and its `capacity` is at least the `size`.
+unsafe fn check_buffer(
+    buffer: *mut c_char,
+    capacity: usize,
+    size: usize,
+    required: *mut usize,
+) -> Status {
+    if !required.is_null() {
+        unsafe { *required = size };
+    }
+    if capacity < size {
+        Status::BufferTooSmall
+    } else if buffer.is_null() {
+        Status::NullPointer
+    } else {
+        Status::Ok
+    }
+}
+
+/// Copy the UTF-8 bytes of `value` followed by a null terminator into the `buffer`.
+///
+/// The `buffer` must have room for the bytes and the null terminator (see `check_buffer()`).
+unsafe fn write_c_str(value: &str, buffer: *mut c_char) {
+    let bytes = value.as_bytes();
+    unsafe {
+        std::ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.cast::<u8>(), bytes.len());
+        *buffer.add(bytes.len()) = 0;
+    }
+}
+
+/// An extended alphabet of (non-ASCII) letters that can be used in a password.
+///
+/// Alphabets are bit flags that can be combined with a bitwise OR.
+/// Each alphabet only includes precomposed letters,
+/// so passwords are always in Unicode Normalization Form C (NFC).
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alphabet {
+    /// The letters of the Latin-1 Supplement block (like `À`, `ç`, and `ß`).
+    Latin1 = 1 << 0,
+
+    /// The letters of the modern Greek alphabet (like `Α`, `β`, and `ς`).
+    Greek = 1 << 1,
+
+    /// The letters of the basic Russian Cyrillic alphabet (like `Ж`, `ё`, and `я`).
+    Cyrillic = 1 << 2,
+}
+
+impl From<Alphabet> for ::mk_pass::Alphabet {
+    fn from(value: Alphabet) -> Self {
+        match value {
+            Alphabet::Latin1 => Self::Latin1,
+            Alphabet::Greek => Self::Greek,
+            Alphabet::Cyrillic => Self::Cyrillic,
         }
-        Err(e) => {
-            eprintln!("Failed to convert rust String to CString: {e:?}");
-            0
+    }
+}
+
+/// Add the letters of the given `alphabets` (a combination of `Alphabet` flags) to the `sets`.
+fn with_alphabets(sets: ::mk_pass::CharacterSets, alphabets: u8) -> ::mk_pass::CharacterSets {
+    [Alphabet::Latin1, Alphabet::Greek, Alphabet::Cyrillic]
+        .into_iter()
+        .filter(|alphabet| alphabets & *alphabet as u8 != 0)
+        .fold(sets, |sets, alphabet| sets.with_alphabet(alphabet.into()))
+}
+
+/// Generate a password that may contain the letters of the given `alphabets`.
+///
+/// This behaves like `mk_pass_generate()`, but the letters of the `alphabets`
+/// (a combination of `Alphabet` flags) are added to the ASCII letters.
+/// The password is written to the `buffer` as UTF-8 followed by a null terminator.
+/// If `required` is not null, then it is set to the number of bytes needed for
+/// the longest possible password and its null terminator.
+/// Each character takes up to 4 bytes, so this is at most
+/// `mk_pass_validate_requirements(&config).length * 4 + 1` bytes.
+///
+/// Returns
+///
+/// - `Status::Ok` if the password was written to the `buffer`.
+/// - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+/// - `Status::NullPointer` if the `buffer` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_generate_unicode(
+    buffer: *mut c_char,
+    capacity: usize,
+    config: PasswordRequirements,
+    alphabets: u8,
+    required: *mut usize,
+) -> Status {
+    let sets = with_alphabets(::mk_pass::CharacterSets::default(), alphabets);
+    let real_config = ::mk_pass::PasswordRequirements::from(config).validate_with(&sets);
+    let widest = [
+        sets.uppercase(),
+        sets.lowercase(),
+        sets.decimal(),
+        sets.special(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|ch| ch.len_utf8())
+    .max()
+    .unwrap_or(1);
+    let size = real_config.length as usize * widest + 1;
+    let status = unsafe { check_buffer(buffer, capacity, size, required) };
+    if status == Status::Ok {
+        let new_password = sets.generate_secret_password(real_config);
+        unsafe { write_c_str(new_password.expose_secret(), buffer) };
+    }
+    status
+}
+
+/// Generate a `count` of passwords given the constraints specified by `config`.
+///
+/// This function will invoke `mk_pass_validate_requirements(&config)` once for the entire batch.
+/// Each password is written to the `buffer` followed by a null terminator.
+/// If `required` is not null, then it is set to the number of bytes needed for
+/// all passwords and their null terminators
+/// (`count * (mk_pass_validate_requirements(&config).length + 1)`).
+///
+/// If `unique` is `true`, then no two passwords in the batch are the same.
+/// In this case, the `count` is limited to the number of possible passwords
+/// that satisfy the `config`.
+/// If `written` is not null, then it is set to the number of passwords written to the `buffer`.
+///
+/// Returns
+///
+/// - `Status::Ok` if the passwords were written to the `buffer`.
+/// - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+/// - `Status::NullPointer` if the `buffer` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_generate_passwords(
+    buffer: *mut c_char,
+    capacity: usize,
+    count: u32,
+    unique: bool,
+    config: PasswordRequirements,
+    written: *mut u32,
+    required: *mut usize,
+) -> Status {
+    if !written.is_null() {
+        unsafe { *written = 0 };
+    }
+    let real_config = ::mk_pass::PasswordRequirements::from(config).validate();
+    let stride = real_config.length as usize + 1;
+    let size = (count as usize).saturating_mul(stride);
+    let status = unsafe { check_buffer(buffer, capacity, size, required) };
+    if status != Status::Ok {
+        return status;
+    }
+    let sets = ::mk_pass::CharacterSets::default();
+    let passwords = sets
+        .passwords(real_config, unique)
+        .take(count as usize)
+        .map(::mk_pass::SecretString::from);
+    let mut total = 0;
+    for (i, password) in passwords.enumerate() {
+        unsafe { write_c_str(password.expose_secret(), buffer.add(i * stride)) };
+        total += 1;
+    }
+    if !written.is_null() {
+        unsafe { *written = total };
+    }
+    Status::Ok
+}
+
+/// Calculate the theoretical entropy (in bits) of a password generated with the given `config`.
+///
+/// This function will invoke `mk_pass_validate_requirements(&config)` to
+/// ensure basic password requirements are met.
+///
+/// The entropy is the base 2 logarithm of the number of possible passwords
+/// that satisfy the `config`.
+#[unsafe(no_mangle)]
+pub extern "C" fn mk_pass_password_entropy(config: PasswordRequirements) -> f64 {
+    ::mk_pass::password_entropy(config.into())
+}
+
+/// Generate a pronounceable password given the constraints specified by `config`.
+///
+/// The letters alternate between consonant clusters (like `th`) and vowel clusters
+/// (like `ou`). The required decimal integers and special characters are inserted
+/// at random positions. The `config` is validated with `allowRepeats` enabled,
+/// so characters may be repeated even if `allowRepeats` is disabled.
+/// The password is written to the `buffer` followed by a null terminator.
+/// If `required` is not null, then it is set to the number of bytes needed for
+/// the password and its null terminator (which does not depend on the random password).
+///
+/// Returns
+///
+/// - `Status::Ok` if the password was written to the `buffer`.
+/// - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+/// - `Status::NullPointer` if the `buffer` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_generate_pronounceable(
+    buffer: *mut c_char,
+    capacity: usize,
+    config: PasswordRequirements,
+    required: *mut usize,
+) -> Status {
+    // validate like `generate_pronounceable_password()`, so the size is known before generating
+    let real_config = ::mk_pass::PasswordRequirements {
+        max_decimal: None,
+        max_specials: None,
+        allow_repeats: true,
+        ..config.into()
+    }
+    .validate();
+    // the default character sets only contain ASCII characters (1 byte each)
+    let size = real_config.length as usize + 1;
+    let status = unsafe { check_buffer(buffer, capacity, size, required) };
+    if status == Status::Ok {
+        let new_password =
+            ::mk_pass::SecretString::from(::mk_pass::generate_pronounceable_password(real_config));
+        unsafe { write_c_str(new_password.expose_secret(), buffer) };
+    }
+    status
+}
+
+/// Calculate the entropy (in bits) of a pronounceable password generated with the given `config`.
+///
+/// Pronounceable passwords have much less entropy than other passwords of the same length.
+/// See also `mk_pass_generate_pronounceable()`.
+#[unsafe(no_mangle)]
+pub extern "C" fn mk_pass_pronounceable_entropy(config: PasswordRequirements) -> f64 {
+    ::mk_pass::pronounceable_entropy(config.into())
+}
+
+/// A kind of character used in a password.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharKind {
+    /// The uppercase letters.
+    Uppercase,
+
+    /// The lowercase letters.
+    Lowercase,
+
+    /// The decimal integers.
+    Decimal,
+
+    /// The special characters.
+    Special,
+}
+
+/// The (opaque) sets of characters sampled when generating a password.
+///
+/// Create the sets with `mk_pass_charsets_new()` and free them with `mk_pass_charsets_free()`.
+/// Functions that only read the sets treat a null pointer as the default character sets.
+pub struct CharacterSets(::mk_pass::CharacterSets);
+
+/// Get the character sets at `sets`, or the default character sets if `sets` is null.
+unsafe fn sets_or_default<'a>(sets: *const CharacterSets) -> Cow<'a, ::mk_pass::CharacterSets> {
+    match unsafe { sets.as_ref() } {
+        Some(sets) => Cow::Borrowed(&sets.0),
+        None => Cow::Owned(::mk_pass::CharacterSets::default()),
+    }
+}
+
+/// Read the characters of a null-terminated UTF-8 `string`.
+unsafe fn read_c_str<'a>(string: *const c_char) -> Result<&'a str, Status> {
+    if string.is_null() {
+        return Err(Status::NullPointer);
+    }
+    unsafe { CStr::from_ptr(string) }
+        .to_str()
+        .map_err(|_| Status::InvalidUtf8)
+}
+
+/// Change the character sets at `sets` with the given function `f`.
+unsafe fn modify_sets(
+    sets: *mut CharacterSets,
+    f: impl FnOnce(::mk_pass::CharacterSets) -> ::mk_pass::CharacterSets,
+) -> Status {
+    match unsafe { sets.as_mut() } {
+        Some(sets) => {
+            sets.0 = f(std::mem::take(&mut sets.0));
+            Status::Ok
         }
+        None => Status::NullPointer,
     }
 }
 
-/// The function used as a native entrypoint for an executable.
+/// Create the default character sets.
+///
+/// The returned sets must be freed with `mk_pass_charsets_free()`.
+#[unsafe(no_mangle)]
+pub extern "C" fn mk_pass_charsets_new() -> *mut CharacterSets {
+    Box::into_raw(Box::new(CharacterSets(::mk_pass::CharacterSets::default())))
+}
+
+/// Create a copy of the given character `sets`.
+///
+/// The returned sets must be freed with `mk_pass_charsets_free()`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_clone(sets: *const CharacterSets) -> *mut CharacterSets {
+    let sets = unsafe { sets_or_default(sets) }.into_owned();
+    Box::into_raw(Box::new(CharacterSets(sets)))
+}
+
+/// Free the character `sets` created by `mk_pass_charsets_new()` or `mk_pass_charsets_clone()`.
+///
+/// Passing null `sets` does nothing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_free(sets: *mut CharacterSets) {
+    if !sets.is_null() {
+        drop(unsafe { Box::from_raw(sets) });
+    }
+}
+
+/// Replace the set of the given `kind` of characters with the null-terminated UTF-8 `chars`.
+///
+/// Each character is normalized to Unicode Normalization Form C (NFC).
+/// Characters that cannot stand alone in NFC (like combining marks) and
+/// duplicate characters are ignored.
+///
+/// Returns `Status::InvalidUtf8` if the `chars` are not valid UTF-8,
+/// or `Status::NullPointer` if the `sets` or `chars` are null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_set(
+    sets: *mut CharacterSets,
+    kind: CharKind,
+    chars: *const c_char,
+) -> Status {
+    let chars = match unsafe { read_c_str(chars) } {
+        Ok(chars) => chars.chars(),
+        Err(status) => return status,
+    };
+    unsafe {
+        modify_sets(sets, |sets| match kind {
+            CharKind::Uppercase => sets.with_uppercase(chars),
+            CharKind::Lowercase => sets.with_lowercase(chars),
+            CharKind::Decimal => sets.with_decimal(chars),
+            CharKind::Special => sets.with_special(chars),
+        })
+    }
+}
+
+/// Add the letters of the given `alphabets` (a combination of `Alphabet` flags)
+/// to the sets of uppercase and lowercase letters.
+///
+/// Returns `Status::NullPointer` if the `sets` are null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_add_alphabets(
+    sets: *mut CharacterSets,
+    alphabets: u8,
+) -> Status {
+    unsafe { modify_sets(sets, |sets| with_alphabets(sets, alphabets)) }
+}
+
+/// Remove the given null-terminated UTF-8 `chars` from all sets.
+///
+/// Returns `Status::InvalidUtf8` if the `chars` are not valid UTF-8,
+/// or `Status::NullPointer` if the `sets` or `chars` are null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_exclude(
+    sets: *mut CharacterSets,
+    chars: *const c_char,
+) -> Status {
+    match unsafe { read_c_str(chars) } {
+        Ok(chars) => unsafe { modify_sets(sets, |sets| sets.exclude(chars.chars())) },
+        Err(status) => status,
+    }
+}
+
+/// Get the set of the given `kind` of characters.
+///
+/// The characters are written to the `buffer` as UTF-8 followed by a null terminator.
+/// If `required` is not null, then it is set to the number of bytes needed for
+/// the characters and the null terminator.
+///
+/// Returns
+///
+/// - `Status::Ok` if the characters were written to the `buffer`.
+/// - `Status::BufferTooSmall` if the `capacity` is less than the required size.
+/// - `Status::NullPointer` if the `buffer` is null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_get(
+    sets: *const CharacterSets,
+    kind: CharKind,
+    buffer: *mut c_char,
+    capacity: usize,
+    required: *mut usize,
+) -> Status {
+    let sets = unsafe { sets_or_default(sets) };
+    let chars = match kind {
+        CharKind::Uppercase => sets.uppercase(),
+        CharKind::Lowercase => sets.lowercase(),
+        CharKind::Decimal => sets.decimal(),
+        CharKind::Special => sets.special(),
+    }
+    .iter()
+    .collect::<String>();
+    let status = unsafe { check_buffer(buffer, capacity, chars.len() + 1, required) };
+    if status == Status::Ok {
+        unsafe { write_c_str(&chars, buffer) };
+    }
+    status
+}
+
+/// Validates the `config` against the given character `sets`.
+///
+/// This behaves like `mk_pass_validate_requirements()`, but the limits that
+/// avoid repetitions are derived from the sizes of the given `sets`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_validate(
+    sets: *const CharacterSets,
+    config: PasswordRequirements,
+) -> PasswordRequirements {
+    let sets = unsafe { sets_or_default(sets) };
+    ::mk_pass::PasswordRequirements::from(config)
+        .validate_with(&sets)
+        .into()
+}
+
+/// Check the `config` against the given character `sets` without changing it.
+///
+/// Returns null if `mk_pass_charsets_validate()` would not change the `config`.
+/// Otherwise, returns a newly allocated message that describes the first violated
+/// requirement. The returned message must be freed with `mk_pass_string_free()`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_check(
+    sets: *const CharacterSets,
+    config: PasswordRequirements,
+) -> *mut c_char {
+    let sets = unsafe { sets_or_default(sets) };
+    match ::mk_pass::PasswordRequirements::from(config).check_with(&sets) {
+        Ok(()) => std::ptr::null_mut(),
+        Err(err) => CString::new(err.to_string()).map_or(std::ptr::null_mut(), CString::into_raw),
+    }
+}
+
+/// Calculate the theoretical entropy (in bits) of a password generated
+/// from the given character `sets` with the given `config`.
+///
+/// See also `mk_pass_password_entropy()`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_entropy(
+    sets: *const CharacterSets,
+    config: PasswordRequirements,
+) -> f64 {
+    unsafe { sets_or_default(sets) }.password_entropy(config.into())
+}
+
+/// Generate a password from the given character `sets` given the constraints
+/// specified by `config`.
+///
+/// This behaves like `mk_pass_generate_alloc()`, but the characters are
+/// sampled from the given `sets`. The returned password must be freed with
+/// `mk_pass_string_free()`.
+///
+/// Returns null if the sets of uppercase and lowercase letters are both empty.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_charsets_generate_alloc(
+    sets: *const CharacterSets,
+    config: PasswordRequirements,
+) -> *mut c_char {
+    let sets = unsafe { sets_or_default(sets) };
+    if sets.uppercase().is_empty() && sets.lowercase().is_empty() {
+        return std::ptr::null_mut();
+    }
+    into_c_string(&sets.generate_secret_password(config.into()))
+}
+
+/// Generate a `count` of passwords from the given character `sets` given the
+/// constraints specified by `config`.
+///
+/// If `unique` is `true`, then no two passwords in the batch are the same.
+/// In this case, the `count` is limited to the number of possible passwords
+/// that satisfy the `config`.
+///
+/// Returns a newly allocated array of newly allocated, null-terminated passwords.
+/// If `written` is not null, then it is set to the number of passwords in the array.
+/// The returned array must be freed with `mk_pass_strings_free()`.
+/// Returns null if the sets of uppercase and lowercase letters are both empty.
 #[unsafe(no_mangle)]
-pub extern "C" fn runMain() {
-    use ::mk_pass::clap::Parser;
+pub unsafe extern "C" fn mk_pass_charsets_generate_many(
+    sets: *const CharacterSets,
+    config: PasswordRequirements,
+    count: u32,
+    unique: bool,
+    written: *mut u32,
+) -> *mut *mut c_char {
+    let sets = unsafe { sets_or_default(sets) };
+    if sets.uppercase().is_empty() && sets.lowercase().is_empty() {
+        return std::ptr::null_mut();
+    }
+    let strings = sets
+        .passwords(config.into(), unique)
+        .take(count as usize)
+        .map(|password| into_c_string(&::mk_pass::SecretString::from(password)))
+        .collect::<Box<[*mut c_char]>>();
+    if !written.is_null() {
+        unsafe { *written = strings.len() as u32 };
+    }
+    Box::into_raw(strings).cast()
+}
 
-    let config = ::mk_pass::PasswordRequirements::parse();
-    let password = ::mk_pass::generate_password(config);
-    println!("{password}");
+/// Free an array of `count` `strings` allocated by this library
+/// (like `mk_pass_charsets_generate_many()`).
+///
+/// Each string is wiped from memory before it is freed.
+/// Passing null `strings` does nothing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mk_pass_strings_free(strings: *mut *mut c_char, count: u32) {
+    if strings.is_null() {
+        return;
+    }
+    let strings =
+        unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(strings, count as usize)) };
+    for string in strings {
+        unsafe { mk_pass_string_free(string) };
+    }
+}
+
+/// The function used as a native entrypoint for an executable.
+#[unsafe(no_mangle)]
+pub extern "C" fn mk_pass_run_main() {
+    ::mk_pass::Cli::parse_args(std::env::args_os()).run();
 }
@@ -0,0 +1,15 @@
+#include <assert.h>
+#include <mk_pass.h>
+#include <stdio.h>
+#include <string.h>
+
+int main(void) {
+    MkPassPasswordRequirements config = mk_pass_default_requirements();
+    config.length = 20;
+    char* password = mk_pass_generate_alloc(config);
+    assert(password != NULL);
+    printf("Generated password: %s\n", password);
+    assert(strlen(password) == 20);
+    mk_pass_string_free(password);
+    return 0;
+}
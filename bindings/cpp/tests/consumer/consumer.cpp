@@ -0,0 +1,12 @@
+#include <cassert>
+#include <iostream>
+#include <mk_pass.hpp>
+#include <string>
+
+int main() {
+    mk_pass::Generator generator;
+    std::string password = generator.length(20).generate();
+    std::cout << "Generated password: " << password << std::endl;
+    assert(password.size() == 20);
+    return 0;
+}
@@ -0,0 +1,95 @@
+#include <assert.h>
+#include <mk_pass.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int main(void) {
+    // the requirements are validated
+    MkPassPasswordRequirements config = mk_pass_default_requirements();
+    config.length = 100;
+    config.decimal = 1;
+    config.specials = 1;
+    MkPassPasswordRequirements validated =
+        mk_pass_validate_requirements(&config);
+    assert(validated.length >= 10);
+    assert(validated.length < config.length);
+    assert(validated.first_is_letter);
+    assert(!validated.allow_repeats);
+
+    MkPassPasswordRequirements pci =
+        mk_pass_preset_requirements(MK_PASS_PRESET_PCI_DSS);
+    assert(pci.length == 12);
+
+    // query the required size, then generate a password into a buffer
+    size_t required = 0;
+    MkPassStatus status = mk_pass_generate(NULL, 0, config, &required);
+    assert(status == MK_PASS_STATUS_BUFFER_TOO_SMALL);
+    assert(required == validated.length + 1u);
+    char* buffer = malloc(required);
+    assert(buffer != NULL);
+    status = mk_pass_generate(buffer, required, config, NULL);
+    assert(status == MK_PASS_STATUS_OK);
+    printf("Generated password: %s\n", buffer);
+    assert(strlen(buffer) == validated.length);
+    free(buffer);
+
+    // an allocated password must be freed by the library
+    char* password = mk_pass_generate_alloc(config);
+    assert(password != NULL);
+    assert(strlen(password) == validated.length);
+    mk_pass_string_free(password);
+
+    // a batch of unique passwords
+    char batch[3 * 17] = {0};
+    uint32_t count = 0;
+    status = mk_pass_generate_passwords(batch, sizeof(batch), 3, true,
+                                        mk_pass_default_requirements(), &count,
+                                        &required);
+    assert(status == MK_PASS_STATUS_OK);
+    assert(count == 3);
+    assert(required == sizeof(batch));
+    assert(strcmp(batch, batch + 17) != 0);
+    status = mk_pass_generate_passwords(batch, sizeof(batch), 4, true,
+                                        mk_pass_default_requirements(), &count,
+                                        NULL);
+    assert(status == MK_PASS_STATUS_BUFFER_TOO_SMALL);
+    assert(count == 0);
+
+    // the letters of extended alphabets take up to 4 bytes each
+    char unicode[16 * 4 + 1] = {0};
+    uint8_t alphabets = MK_PASS_ALPHABET_GREEK | MK_PASS_ALPHABET_CYRILLIC;
+    status = mk_pass_generate_unicode(unicode, sizeof(unicode),
+                                      mk_pass_default_requirements(), alphabets,
+                                      &required);
+    assert(status == MK_PASS_STATUS_OK);
+    assert(required <= sizeof(unicode));
+    assert(strlen(unicode) >= 16);
+
+    char pronounceable[17] = {0};
+    status = mk_pass_generate_pronounceable(
+        pronounceable, sizeof(pronounceable), mk_pass_default_requirements(),
+        &required);
+    assert(status == MK_PASS_STATUS_OK);
+    assert(required == sizeof(pronounceable));
+    assert(strlen(pronounceable) == 16);
+
+    // custom character sets
+    MkPassCharacterSets* sets = mk_pass_charsets_new();
+    assert(sets != NULL);
+    status = mk_pass_charsets_exclude(sets, MK_PASS_AMBIGUOUS_CHARACTERS);
+    assert(status == MK_PASS_STATUS_OK);
+    status = mk_pass_charsets_set(sets, MK_PASS_CHAR_KIND_SPECIAL, "-_");
+    assert(status == MK_PASS_STATUS_OK);
+    password = mk_pass_charsets_generate_alloc(sets, config);
+    assert(password != NULL);
+    printf("Generated password: %s\n", password);
+    assert(strpbrk(password, MK_PASS_AMBIGUOUS_CHARACTERS) == NULL);
+    assert(strpbrk(password, "/\\:`+&,@$!#%~.") == NULL);
+    mk_pass_string_free(password);
+    mk_pass_charsets_free(sets);
+
+    assert(mk_pass_password_entropy(config) > 0);
+    assert(mk_pass_pronounceable_entropy(config) > 0);
+    return 0;
+}
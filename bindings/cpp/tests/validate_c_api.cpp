@@ -0,0 +1,56 @@
+#include <cassert>
+#include <cstring>
+#include <iostream>
+#include <mk_pass.hpp>
+#include <vector>
+
+int main() {
+    mk_pass::PasswordRequirements config = {
+        100,   // length
+        1,     // decimal
+        1,     // specials
+        true,  // firstIsLetter
+        false, // allowRepeats
+    };
+    // query the required size; the length is shortened to avoid repetitions
+    uint16_t length = mk_pass::validateRequirements(&config).length;
+    assert(length < config.length);
+    size_t required = 0;
+    mk_pass::Status status =
+        mk_pass::mk_pass_generate(nullptr, 0, config, &required);
+    assert(status == mk_pass::Status::BufferTooSmall);
+    assert(required == length + 1u);
+
+    // a buffer that is too small is not written
+    std::vector<char> buffer(required - 1, 'x');
+    status = mk_pass::mk_pass_generate(buffer.data(), buffer.size(), config,
+                                       &required);
+    assert(status == mk_pass::Status::BufferTooSmall);
+    for (char ch : buffer) {
+        assert(ch == 'x');
+    }
+
+    // a buffer of the required size is filled and null terminated
+    buffer.resize(required, 0);
+    status = mk_pass::mk_pass_generate(buffer.data(), buffer.size(), config,
+                                       nullptr);
+    assert(status == mk_pass::Status::Ok);
+    std::cout << "Password: " << buffer.data() << std::endl;
+    assert(strlen(buffer.data()) == length);
+
+    status = mk_pass::mk_pass_generate(nullptr, 100, config, nullptr);
+    assert(status == mk_pass::Status::NullPointer);
+
+    // an allocated password must be freed by the library
+    char* password = mk_pass::mk_pass_generate_alloc(config);
+    assert(password != nullptr);
+    assert(strlen(password) == length);
+    mk_pass::mk_pass_string_free(password);
+    mk_pass::mk_pass_string_free(nullptr);
+
+    mk_pass::UniqueString managed(mk_pass::mk_pass_generate_alloc(config));
+    assert(strlen(managed.get()) == length);
+    assert(mk_pass::generatePassword(config).size() == length);
+
+    return 0;
+}
@@ -7,6 +7,8 @@ void printConfig(mk_pass::PasswordRequirements &config) {
               << "length = " << config.length << "\n\t"
               << "decimal = " << config.decimal << "\n\t"
               << "specials = " << config.specials << "\n\t"
+              << "uppercase = " << config.uppercase << "\n\t"
+              << "lowercase = " << config.lowercase << "\n\t"
               << "firstIsLetter = " << config.firstIsLetter << "\n}"
               << std::endl;
 }
@@ -21,7 +23,7 @@ int main() {
     };
     mk_pass::PasswordRequirements expected = {
         16,    // length
-        13,    // decimal
+        10,    // decimal
         1,     // specials
         true,  // firstIsLetter
         false, // allowRepeats
@@ -31,5 +33,19 @@ int main() {
     printConfig(config);
     printConfig(validated);
     assert(validated == expected);
+
+    // uppercase letters cannot overrule the other required characters
+    config.uppercase = 20;
+    validated = mk_pass::validateRequirements(&config);
+    printConfig(validated);
+    assert(validated.uppercase == 13);
+
+    // a maximum count cannot be less than its minimum count
+    config.uppercase = 0;
+    config.decimal = 3;
+    config.maxDecimal = 2;
+    validated = mk_pass::validateRequirements(&config);
+    printConfig(validated);
+    assert(validated.maxDecimal == 3);
     return 0;
 }
@@ -0,0 +1,24 @@
+#include <cassert>
+#include <cmath>
+#include <iostream>
+#include <mk_pass.hpp>
+
+int main() {
+    mk_pass::PasswordRequirements config = {
+        16,    // length
+        1,     // decimal
+        1,     // specials
+        true,  // firstIsLetter
+        false, // allowRepeats
+    };
+    double entropy = mk_pass::passwordEntropy(config);
+    std::cout << "Entropy: " << entropy << " bits" << std::endl;
+    assert(std::round(entropy * 100) == 9003);
+
+    config.allowRepeats = true;
+    double withRepeats = mk_pass::passwordEntropy(config);
+    std::cout << "Entropy (with repeats): " << withRepeats << " bits"
+              << std::endl;
+    assert(withRepeats > entropy);
+    return 0;
+}
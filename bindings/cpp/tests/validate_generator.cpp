@@ -0,0 +1,92 @@
+#include <cassert>
+#include <cstring>
+#include <iostream>
+#include <mk_pass.hpp>
+#include <set>
+#include <string>
+#include <vector>
+
+int main() {
+    // the character set constants are exported
+    assert(strlen(mk_pass::SPECIAL_CHARACTERS) == 16);
+    assert(std::string(mk_pass::DECIMAL) == "0123456789");
+    assert(mk_pass::UPPERCASE[0] == 'A' && strlen(mk_pass::UPPERCASE) == 26);
+    assert(mk_pass::LOWERCASE[25] == 'z' && strlen(mk_pass::LOWERCASE) == 26);
+
+    mk_pass::Generator defaults;
+    assert(defaults.requirements() == mk_pass::mk_pass_default_requirements());
+    assert(defaults.generate().size() == 16);
+    assert(defaults.entropy()
+           == mk_pass::passwordEntropy(defaults.requirements()));
+    assert(defaults.characters(mk_pass::CharKind::Decimal) == mk_pass::DECIMAL);
+
+    // builder-style setters can be chained
+    mk_pass::Generator generator = mk_pass::Generator()
+                                       .length(20)
+                                       .decimal(3)
+                                       .specials(2)
+                                       .withSpecial("-_")
+                                       .withoutAmbiguous();
+    assert(generator.characters(mk_pass::CharKind::Special) == "-_");
+    assert(generator.characters(mk_pass::CharKind::Uppercase).find('O')
+           == std::string::npos);
+    std::string password = generator.generate();
+    std::cout << "Password: " << password << std::endl;
+    assert(password.size() == 20);
+    size_t specials = 0;
+    for (char ch : password) {
+        specials += static_cast<size_t>(ch == '-' || ch == '_');
+    }
+    assert(specials == 2);
+
+    // copies do not share character sets
+    mk_pass::Generator copy = generator;
+    copy.withSpecial("!");
+    assert(copy.characters(mk_pass::CharKind::Special) == "!");
+    assert(generator.characters(mk_pass::CharKind::Special) == "-_");
+
+    // batches of passwords
+    std::vector<std::string> passwords = generator.generate(5, true);
+    assert(passwords.size() == 5);
+    std::set<std::string> unique(passwords.begin(), passwords.end());
+    assert(unique.size() == 5);
+
+    mk_pass::SecretString secret = generator.generateSecret();
+    assert(secret.exposeSecret().size() == 20);
+
+    // extended alphabets
+    mk_pass::Generator greek =
+        mk_pass::Generator().withAlphabet(mk_pass::Alphabet::Greek);
+    assert(greek.characters(mk_pass::CharKind::Uppercase).find("Ω")
+           != std::string::npos);
+
+    // requirements are validated unless the generator is strict
+    mk_pass::Generator tooShort = mk_pass::Generator().length(8);
+    assert(tooShort.validated().length == 10);
+    assert(tooShort.generate().size() == 10);
+    bool thrown = false;
+    try {
+        tooShort.strict().generate();
+    } catch (const mk_pass::RequirementsError& err) {
+        std::cout << "Error: " << err.what() << std::endl;
+        thrown = true;
+    }
+    assert(thrown);
+
+    thrown = false;
+    try {
+        mk_pass::Generator().withSpecial("\xff");
+    } catch (const mk_pass::Error& err) {
+        thrown = true;
+    }
+    assert(thrown);
+
+    thrown = false;
+    try {
+        mk_pass::Generator().withUppercase("").withLowercase("").generate();
+    } catch (const mk_pass::Error& err) {
+        thrown = true;
+    }
+    assert(thrown);
+    return 0;
+}
@@ -1,9 +1,9 @@
 #include <cassert>
 #include <cctype>
 #include <cstdint>
-#include <cstring>
 #include <iostream>
 #include <mk_pass.hpp>
+#include <string>
 
 enum CharType {
     Decimal,
@@ -12,10 +12,9 @@ enum CharType {
     Special,
 };
 
-uint16_t count(char* str_buf, uint16_t len, CharType char_t) {
+uint16_t count(const std::string& password, CharType char_t) {
     uint16_t total = 0;
-    for (uint16_t i = 0; i < len; ++i) {
-        char ch = str_buf[i];
+    for (char ch : password) {
         switch (char_t) {
         case Decimal:
             total += static_cast<uint16_t>(static_cast<bool>(isdigit(ch)));
@@ -42,28 +41,21 @@ int main() {
         true,  // firstIsLetter
         false, // allowRepeats
     };
-    // allocate a buffer on the heap. length + 1 allows for null terminator
-    char* buf = new char[config.length + 1];
-    // initialize the buf so that the null terminator is only at the end
-    memset(buf, '\n', config.length);
-    buf[config.length] = 0;
+    std::string password = mk_pass::generatePassword(config);
+    std::cout << "Password: " << password << std::endl;
 
-    uint16_t len = mk_pass::generatePassword(buf, config);
-    std::cout << "Password: " << buf << std::endl;
-
-    uint16_t decimal = count(buf, config.length, CharType::Decimal);
-    uint16_t special = count(buf, config.length, CharType::Special);
-    uint16_t lowercase = count(buf, config.length, CharType::Lowercase);
-    uint16_t uppercase = count(buf, config.length, CharType::Uppercase);
+    uint16_t decimal = count(password, CharType::Decimal);
+    uint16_t special = count(password, CharType::Special);
+    uint16_t lowercase = count(password, CharType::Lowercase);
+    uint16_t uppercase = count(password, CharType::Uppercase);
     std::cout << "Decimal: " << decimal << ", "
               << "Specials: " << special << ", "
               << "Lowercase: " << lowercase << ", "
               << "Uppercase: " << uppercase << std::endl;
 
-    assert(static_cast<bool>(islower(buf[0]))
-           || static_cast<bool>(isupper(buf[0])));
-    delete[] buf;
-    assert(len == config.length);
+    assert(static_cast<bool>(islower(password[0]))
+           || static_cast<bool>(isupper(password[0])));
+    assert(password.size() == config.length);
     assert(decimal == config.decimal);
     assert(special == config.specials);
     uint16_t letters = lowercase + uppercase;
@@ -0,0 +1,26 @@
+#include <cassert>
+#include <iostream>
+#include <mk_pass.hpp>
+#include <set>
+#include <string>
+#include <vector>
+
+int main() {
+    mk_pass::PasswordRequirements config = {
+        16,    // length
+        1,     // decimal
+        1,     // specials
+        true,  // firstIsLetter
+        false, // allowRepeats
+    };
+    std::vector<std::string> passwords =
+        mk_pass::generatePasswords(config, 10, true);
+    assert(passwords.size() == 10);
+    for (const std::string& password : passwords) {
+        std::cout << "Password: " << password << std::endl;
+        assert(password.size() == config.length);
+    }
+    std::set<std::string> unique(passwords.begin(), passwords.end());
+    assert(unique.size() == passwords.size());
+    return 0;
+}
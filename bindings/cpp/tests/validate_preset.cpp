@@ -0,0 +1,21 @@
+#include <cassert>
+#include <iostream>
+#include <mk_pass.hpp>
+#include <string>
+
+int main() {
+    mk_pass::PasswordRequirements config =
+        mk_pass::presetRequirements(mk_pass::Preset::PciDss);
+    assert(config.length == 12);
+    assert(config.decimal == 1);
+    assert(mk_pass::validateRequirements(&config) == config);
+
+    std::string password = mk_pass::generatePassword(config);
+    std::cout << "Generated password: " << password << std::endl;
+    assert(password.size() == 12);
+
+    mk_pass::PasswordRequirements nist =
+        mk_pass::presetRequirements(mk_pass::Preset::Nist80063b);
+    assert(nist.length == 15);
+    return 0;
+}
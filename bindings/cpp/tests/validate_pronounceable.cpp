@@ -0,0 +1,39 @@
+#include <cassert>
+#include <cctype>
+#include <iostream>
+#include <mk_pass.hpp>
+
+int main() {
+    mk_pass::PasswordRequirements config = {
+        20,    // length
+        2,     // decimal
+        1,     // specials
+        true,  // firstIsLetter
+        false, // allowRepeats
+    };
+    std::string password = mk_pass::generatePronounceablePassword(config);
+    std::cout << "Generated password: " << password << std::endl;
+    assert(password.size() == 20);
+    assert(std::isalpha(static_cast<unsigned char>(password[0])));
+    size_t decimal = 0;
+    for (char ch : password) {
+        if (std::isdigit(static_cast<unsigned char>(ch))) {
+            ++decimal;
+        }
+    }
+    assert(decimal == 2);
+
+    double entropy = mk_pass::pronounceableEntropy(config);
+    std::cout << "Entropy: " << entropy << " bits" << std::endl;
+    assert(entropy > 0);
+    assert(entropy < mk_pass::passwordEntropy(config));
+
+    size_t required = 0;
+    char small[8] = {0};
+    mk_pass::Status status = mk_pass::generatePronounceablePassword(
+        small, sizeof(small), config, &required);
+    assert(status == mk_pass::Status::BufferTooSmall);
+    assert(required == 20 + 1u);
+    assert(small[0] == 0);
+    return 0;
+}
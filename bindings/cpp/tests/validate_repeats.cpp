@@ -1,13 +1,14 @@
 #include <cassert>
 #include <cctype>
 #include <cstdint>
-#include <cstring>
 #include <iostream>
 #include <mk_pass.hpp>
+#include <string>
 
-uint16_t count(char* str_buf, uint16_t len) {
+uint16_t count(const std::string& str_buf) {
+    uint16_t len = static_cast<uint16_t>(str_buf.size());
     uint16_t repeatsLength = 0;
-    char* repeats = new char[len];
+    std::string repeats(len, 0);
     for (uint16_t i = 0; i < len; ++i) {
         char ch = str_buf[i];
         for (uint16_t j = 0; j < i; ++j) {
@@ -37,18 +38,11 @@ int main() {
         true, // firstIsLetter
         true, // allowRepeats
     };
-    // allocate a buffer on the heap. length + 1 allows for null terminator
-    char* buf = new char[config.length + 1];
-    // initialize the buf so that the null terminator is only at the end
-    memset(buf, '\n', config.length);
-    buf[config.length] = 0;
+    std::string password = mk_pass::generatePassword(config);
+    std::cout << "Password: " << password << std::endl;
+    assert(password.size() == config.length);
 
-    uint16_t len = mk_pass::generatePassword(buf, config);
-    std::cout << "Password: " << buf << std::endl;
-    assert(len == config.length);
-
-    uint16_t repeats = count(buf, config.length);
-    delete[] buf;
+    uint16_t repeats = count(password);
     std::cout << "Repeats: " << repeats << std::endl;
     assert((repeats > 0) == config.allowRepeats);
 
@@ -0,0 +1,33 @@
+#include <cassert>
+#include <iostream>
+#include <sstream>
+#include <mk_pass.hpp>
+
+int main() {
+    mk_pass::PasswordRequirements config = {
+        16,    // length
+        1,     // decimal
+        1,     // specials
+        true,  // firstIsLetter
+        false, // allowRepeats
+    };
+    mk_pass::SecretString secret = mk_pass::generateSecretPassword(config);
+    const std::string& password = secret.exposeSecret();
+    assert(password.size() == 16);
+
+    std::ostringstream stream;
+    stream << secret;
+    std::cout << "Generated password: " << stream.str() << std::endl;
+    assert(stream.str() == "[REDACTED]");
+
+    mk_pass::SecretString moved = std::move(secret);
+    assert(moved.exposeSecret().size() == 16);
+    assert(secret.exposeSecret().empty());
+
+    char buffer[32] = {1};
+    mk_pass::secureWipe(buffer, sizeof(buffer));
+    for (char ch : buffer) {
+        assert(ch == 0);
+    }
+    return 0;
+}
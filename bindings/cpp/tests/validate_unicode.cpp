@@ -0,0 +1,52 @@
+#include <cassert>
+#include <cstring>
+#include <iostream>
+#include <mk_pass.hpp>
+#include <string>
+
+// Count the code points of a UTF-8 string (bytes that are not continuations).
+size_t countChars(const std::string& utf8) {
+    size_t total = 0;
+    for (unsigned char byte : utf8) {
+        if ((byte & 0xC0) != 0x80) {
+            total += 1;
+        }
+    }
+    return total;
+}
+
+int main() {
+    mk_pass::PasswordRequirements config = {
+        40,    // length
+        1,     // decimal
+        1,     // specials
+        true,  // firstIsLetter
+        false, // allowRepeats
+    };
+    std::string password = mk_pass::generatePassword(
+        config, mk_pass::Alphabet::Greek | mk_pass::Alphabet::Cyrillic);
+    std::cout << "Password: " << password << std::endl;
+    assert(countChars(password) == 40);
+    assert(password.size() > 40);
+
+    password = mk_pass::generatePassword(config, mk_pass::Alphabet::Latin1);
+    assert(countChars(password) == 40);
+
+    // a buffer that is too small is not written
+    size_t required = 0;
+    char small[8];
+    memset(small, 0, sizeof(small));
+    mk_pass::Status status = mk_pass::generateUnicodePassword(
+        small, sizeof(small), config, 0, &required);
+    assert(status == mk_pass::Status::BufferTooSmall);
+    assert(required == 40 + 1u);
+    assert(small[0] == 0);
+
+    // the required size fits the widest characters of the alphabets
+    status = mk_pass::generateUnicodePassword(
+        nullptr, 0, config, static_cast<uint8_t>(mk_pass::Alphabet::Greek),
+        &required);
+    assert(status == mk_pass::Status::BufferTooSmall);
+    assert(required == 40 * 2 + 1u);
+    return 0;
+}
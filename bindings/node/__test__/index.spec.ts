@@ -1,5 +1,17 @@
 import test, { ExecutionContext } from 'ava'
-import { main, generatePassword, validateRequirements, Samples, PasswordRequirements } from '../index'
+import {
+  main,
+  generatePassword,
+  generatePasswords,
+  validateRequirements,
+  passwordEntropy,
+  generatePronounceablePassword,
+  pronounceableEntropy,
+  generateSecretPassword,
+  presetRequirements,
+  Samples,
+  PasswordRequirements,
+} from '../index'
 
 test('main', async (t) => {
   // just ensure the main() function did not panic.
@@ -13,8 +25,10 @@ test('validateRequirements', (t) => {
   }
   const expected = {
     length: 16,
-    decimal: 13,
+    decimal: 10,
     specials: 1,
+    uppercase: 0,
+    lowercase: 0,
     firstIsLetter: true,
     allowRepeats: false,
   }
@@ -72,8 +86,86 @@ test('generatePassword', (t) => {
   assertPasswordIsExpected(t, password, {})
 })
 
+test('generatePasswords', (t) => {
+  const passwords = generatePasswords({}, 10, true)
+  t.is(passwords.length, 10)
+  t.is(new Set(passwords).size, 10)
+  for (const password of passwords) {
+    assertPasswordIsExpected(t, password, {})
+  }
+})
+
 test('allowRepeats', (t) => {
   const config = { length: 20, decimal: 18, specials: 0, allowRepeats: true }
   const password = generatePassword(config)
   assertPasswordIsExpected(t, password, config)
 })
+
+test('letterCounts', (t) => {
+  const config = { uppercase: 10, lowercase: 2 }
+  const password = generatePassword(config)
+  assertPasswordIsExpected(t, password, {})
+  t.true(countChars(UPPERCASE, password) >= 10)
+  t.true(countChars(LOWERCASE, password) >= 2)
+})
+
+test('countRanges', (t) => {
+  const config = { decimal: 1, maxDecimal: 4, specials: 0, maxSpecials: 3 }
+  for (let i = 0; i < 20; i++) {
+    const password = generatePassword(config)
+    t.is(password.length, 16)
+    const decimals = countChars(DECIMAL, password)
+    t.true(decimals >= 1 && decimals <= 4)
+    t.true(countChars(SPECIAL_CHARACTERS, password) <= 3)
+  }
+})
+
+test('passwordEntropy', (t) => {
+  t.is(passwordEntropy({}).toFixed(2), '90.03')
+  t.true(passwordEntropy({ allowRepeats: true }) > passwordEntropy({}))
+})
+
+test('presetRequirements', (t) => {
+  const config = presetRequirements('pci-dss')
+  t.is(config.length, 12)
+  t.is(config.decimal, 1)
+  t.deepEqual(validateRequirements(config), config)
+  const password = generatePassword(config)
+  t.is(password.length, 12)
+  t.true(countChars(DECIMAL, password) >= 1)
+  t.is(presetRequirements('nist-800-63b').length, 15)
+})
+
+test('alphabets', (t) => {
+  const config = { length: 40 }
+  const password = generatePassword(config, ['greek', 'cyrillic'])
+  t.is([...password].length, 40)
+  t.is(password.normalize('NFC'), password)
+  t.true([...password].some((ch) => ch.charCodeAt(0) > 0x7f))
+  const passwords = generatePasswords(config, 3, false, ['latin1'])
+  t.is(passwords.length, 3)
+  for (const pass of passwords) {
+    t.is([...pass].length, 40)
+  }
+  t.true(passwordEntropy(config, ['greek']) > passwordEntropy(config))
+})
+
+test('pronounceable', (t) => {
+  const config = { length: 20, decimal: 2 }
+  const password = generatePronounceablePassword(config)
+  t.is(password.length, 20)
+  t.is(countChars(DECIMAL, password), 2)
+  t.regex(password, /^[a-zA-Z]/)
+  const entropy = pronounceableEntropy(config)
+  t.true(entropy > 0)
+  t.true(entropy < passwordEntropy(config))
+})
+
+test('secretPassword', (t) => {
+  const secret = generateSecretPassword({})
+  const password = secret.exposeSecret()
+  t.is(password.length, 16)
+  t.is(secret.toString(), '[REDACTED]')
+  t.false(`${secret}`.includes(password))
+  t.false(JSON.stringify({ secret }).includes(password))
+})
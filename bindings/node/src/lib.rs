@@ -1,12 +1,10 @@
-use ::mk_pass::clap::Parser;
+use napi::{Env, JsString, Result};
 use napi_derive::napi;
 
 /// The function used as a native entrypoint for the executable script.
 #[napi]
 pub fn main(args: Vec<String>) {
-    let config = ::mk_pass::PasswordRequirements::parse_from(args);
-    let password = ::mk_pass::generate_password(config);
-    println!("{password}");
+    ::mk_pass::Cli::parse_args(args).run();
 }
 
 /// An object used to describe the requirements for generating a password.
@@ -22,6 +20,28 @@ pub struct PasswordRequirements {
     /// How many special characters should the password contain?
     pub specials: Option<i32>,
 
+    /// At least how many uppercase letters should the password contain?
+    pub uppercase: Option<i32>,
+
+    /// At least how many lowercase letters should the password contain?
+    pub lowercase: Option<i32>,
+
+    /// At most how many numeric characters should the password contain?
+    ///
+    /// If specified, the actual count is picked randomly from `decimal` to this value.
+    pub max_decimal: Option<i32>,
+
+    /// At most how many special characters should the password contain?
+    ///
+    /// If specified, the actual count is picked randomly from `specials` to this value.
+    pub max_specials: Option<i32>,
+
+    /// At most how many uppercase letters should the password contain?
+    pub max_uppercase: Option<i32>,
+
+    /// At most how many lowercase letters should the password contain?
+    pub max_lowercase: Option<i32>,
+
     /// Should the first character always be a letter?
     pub first_is_letter: Option<bool>,
 
@@ -35,6 +55,12 @@ impl From<&PasswordRequirements> for ::mk_pass::PasswordRequirements {
             length: value.length.unwrap_or(16) as u16,
             decimal: value.decimal.unwrap_or(1) as u16,
             specials: value.specials.unwrap_or(1) as u16,
+            uppercase: value.uppercase.unwrap_or(0) as u16,
+            lowercase: value.lowercase.unwrap_or(0) as u16,
+            max_decimal: value.max_decimal.map(|v| v as u16),
+            max_specials: value.max_specials.map(|v| v as u16),
+            max_uppercase: value.max_uppercase.map(|v| v as u16),
+            max_lowercase: value.max_lowercase.map(|v| v as u16),
             first_is_letter: value.first_is_letter.unwrap_or(true),
             allow_repeats: value.allow_repeats.unwrap_or(false),
         }
@@ -47,12 +73,68 @@ impl From<::mk_pass::PasswordRequirements> for PasswordRequirements {
             length: Some(value.length as i32),
             decimal: Some(value.decimal as i32),
             specials: Some(value.specials as i32),
+            uppercase: Some(value.uppercase as i32),
+            lowercase: Some(value.lowercase as i32),
+            max_decimal: value.max_decimal.map(i32::from),
+            max_specials: value.max_specials.map(i32::from),
+            max_uppercase: value.max_uppercase.map(i32::from),
+            max_lowercase: value.max_lowercase.map(i32::from),
             first_is_letter: Some(value.first_is_letter),
             allow_repeats: Some(value.allow_repeats),
         }
     }
 }
 
+/// A named set of password requirements derived from a published standard.
+///
+/// Standards that forbid composition rules are encoded with a minimum count of `0`
+/// and no effective maximum count for decimal integers and special characters.
+#[napi(string_enum)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Preset {
+    /// NIST Special Publication 800-63B (revision 4), section 3.1.1.2.
+    ///
+    /// At least 15 characters and no composition rules.
+    #[napi(value = "nist-800-63b")]
+    Nist80063b,
+
+    /// PCI DSS (version 4.0.1), requirement 8.3.6.
+    ///
+    /// At least 12 characters, including numeric and alphabetic characters.
+    #[napi(value = "pci-dss")]
+    PciDss,
+
+    /// CIS Critical Security Controls (version 8), safeguard 5.2.
+    ///
+    /// At least 14 characters (for accounts without multi-factor authentication).
+    #[napi(value = "cis")]
+    Cis,
+
+    /// OWASP Application Security Verification Standard (version 4.0.3),
+    /// requirements 2.1.1 and 2.1.9.
+    ///
+    /// At least 12 characters and no composition rules.
+    #[napi(value = "owasp-asvs")]
+    OwaspAsvs,
+}
+
+impl From<Preset> for ::mk_pass::Preset {
+    fn from(value: Preset) -> Self {
+        match value {
+            Preset::Nist80063b => Self::Nist80063b,
+            Preset::PciDss => Self::PciDss,
+            Preset::Cis => Self::Cis,
+            Preset::OwaspAsvs => Self::OwaspAsvs,
+        }
+    }
+}
+
+/// Get the password requirements of the given {@link Preset}.
+#[napi]
+pub fn preset_requirements(preset: Preset) -> PasswordRequirements {
+    ::mk_pass::PasswordRequirements::preset(preset.into()).into()
+}
+
 /// Validates the given {@link PasswordRequirements} instance's values.
 ///
 /// This returns a mutated copy of the `config` instance where
@@ -62,26 +144,39 @@ impl From<::mk_pass::PasswordRequirements> for PasswordRequirements {
 /// The phrase "sane minimum requirements" implies
 ///
 /// 1. `length` is not less than 10
-/// 2. To avoid repetitions, `length` is not more than
+/// 2. To avoid repetitions, `length` is not more than the 52 letters plus
+///    the (maximum) counts of decimal integers and special characters. For example,
 ///
-///    - 52 if only letters (no decimal integers or special characters) are used
-///    - 62 if only letters and decimal integers are used
-///    - 68 if only letters and special characters are used
-///    - 78 if letters, decimal integers, and special characters are used
+///    - 54 if the password contains 1 decimal integer and 1 special character (the default)
+///    - 78 if the password contains 10 decimal integers and 16 special characters
 ///    - 65535 if repeated characters are allowed
-/// 3. `specials` character count does not overrule the required number of
+/// 3. `uppercase` and `lowercase` letter counts do not overrule the required number of
+///
+///    - lowercase letters (1; if `uppercase` is the overruling count)
+///    - decimal integers (1; if `decimal` is specified as non-zero value)
+///    - special characters (1; if `specials` is specified as non-zero value)
+/// 4. `specials` character count does not overrule the required number of
 ///
-///    - letters (2; 1 uppercase and 1 lowercase)
+///    - letters (at least 2; 1 uppercase and 1 lowercase or
+///      the specified `uppercase` and `lowercase` counts)
 ///    - decimal integers (if `decimal` is specified as non-zero value)
-/// 4. `decimal` character count does not overrule the required number of
+/// 5. `decimal` character count does not overrule the required number of
 ///
-///    - letters (2; 1 uppercase and 1 lowercase)
+///    - letters (at least 2; 1 uppercase and 1 lowercase or
+///      the specified `uppercase` and `lowercase` counts)
 ///    - special characters (if `specials` is specified as non-zero value)
+/// 6. To avoid repetitions, `decimal` is not more than 10, `specials` is not more than 16,
+///    and `uppercase` and `lowercase` are each not more than 26.
+///    Consequently, `length` is shortened if there are not enough letters (52)
+///    to fill the remainder.
+/// 7. Each maximum count (like `maxDecimal`) is not less than the respective minimum count.
+///    Consequently, `length` is shortened if the maximum counts cannot fill it.
 ///
 /// # About resolving conflicts
 ///
 /// If this function finds a conflict between the specified number of
 /// `specials` characters and `decimal`, then decimal integers takes precedence.
+/// Likewise, `uppercase` letters take precedence over `lowercase` letters.
 ///
 /// For example:
 ///
@@ -95,7 +190,7 @@ impl From<::mk_pass::PasswordRequirements> for PasswordRequirements {
 /// };
 /// let expected = {
 ///   length: 16,
-///   decimal: 13,
+///   decimal: 10,
 ///   specials: 1,
 ///   firstIsLetter: true
 /// };
@@ -110,13 +205,184 @@ pub fn validate_requirements(config: PasswordRequirements) -> PasswordRequiremen
     req.validate().into()
 }
 
+/// An extended alphabet of (non-ASCII) letters that can be used in a password.
+///
+/// Each alphabet only includes precomposed letters,
+/// so passwords are always in Unicode Normalization Form C (NFC).
+#[napi(string_enum)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Alphabet {
+    /// The letters of the Latin-1 Supplement block (like `À`, `ç`, and `ß`).
+    #[napi(value = "latin1")]
+    Latin1,
+
+    /// The letters of the modern Greek alphabet (like `Α`, `β`, and `ς`).
+    #[napi(value = "greek")]
+    Greek,
+
+    /// The letters of the basic Russian Cyrillic alphabet (like `Ж`, `ё`, and `я`).
+    #[napi(value = "cyrillic")]
+    Cyrillic,
+}
+
+impl From<Alphabet> for ::mk_pass::Alphabet {
+    fn from(value: Alphabet) -> Self {
+        match value {
+            Alphabet::Latin1 => Self::Latin1,
+            Alphabet::Greek => Self::Greek,
+            Alphabet::Cyrillic => Self::Cyrillic,
+        }
+    }
+}
+
+/// The character sets with the letters of the given `alphabets` (if any).
+fn character_sets(alphabets: Option<Vec<Alphabet>>) -> ::mk_pass::CharacterSets {
+    alphabets
+        .unwrap_or_default()
+        .into_iter()
+        .fold(::mk_pass::CharacterSets::default(), |sets, alphabet| {
+            sets.with_alphabet(alphabet.into())
+        })
+}
+
+/// A secret string (like a generated password) that is wiped from memory when dropped.
+///
+/// The secret is not revealed by `toString()` or `JSON.stringify()`.
+/// Use {@link SecretString.exposeSecret} to access the secret.
+#[napi]
+pub struct SecretString {
+    secret: ::mk_pass::SecretString,
+}
+
+#[napi]
+impl SecretString {
+    /// Get the secret.
+    ///
+    /// The returned string is a copy that is not wiped from memory.
+    #[napi(ts_return_type = "string")]
+    pub fn expose_secret<'env>(&self, env: &'env Env) -> Result<JsString<'env>> {
+        env.create_string(self.secret.expose_secret())
+    }
+
+    /// A redacted representation of the secret.
+    #[napi(js_name = "toString")]
+    pub fn redacted(&self) -> String {
+        "[REDACTED]".to_string()
+    }
+
+    /// A redacted representation of the secret used by `JSON.stringify()`.
+    #[napi(js_name = "toJSON")]
+    pub fn to_json(&self) -> String {
+        self.redacted()
+    }
+}
+
+/// Copy the given `secret` into a JavaScript string, then wipe the `secret` from memory.
+fn to_js_string(env: &Env, secret: String) -> Result<JsString<'_>> {
+    let secret = ::mk_pass::SecretString::from(secret);
+    env.create_string(secret.expose_secret())
+}
+
 /// Generate a password given the constraints specified by `config`.
 ///
 /// This function will invoke {@link validateRequirements} on the given `config`
 /// to ensure basic password requirements are met.
+///
+/// The letters of any given `alphabets` are added to the ASCII letters.
+/// The password's length is counted in characters (Unicode code points),
+/// so use `[...password].length` to count them in JavaScript.
+#[napi(ts_return_type = "string")]
+pub fn generate_password<'env>(
+    env: &'env Env,
+    config: PasswordRequirements,
+    alphabets: Option<Vec<Alphabet>>,
+) -> Result<JsString<'env>> {
+    to_js_string(
+        env,
+        character_sets(alphabets).generate_password(::mk_pass::PasswordRequirements::from(&config)),
+    )
+}
+
+/// Generate a password given the constraints specified by `config`.
+///
+/// This behaves like {@link generatePassword}, but the password is returned as a
+/// {@link SecretString} that is wiped from memory when it is garbage collected.
+#[napi]
+pub fn generate_secret_password(
+    config: PasswordRequirements,
+    alphabets: Option<Vec<Alphabet>>,
+) -> SecretString {
+    SecretString {
+        secret: character_sets(alphabets)
+            .generate_secret_password(::mk_pass::PasswordRequirements::from(&config)),
+    }
+}
+
+/// Generate a `count` of passwords given the constraints specified by `config`.
+///
+/// This function will invoke {@link validateRequirements} on the given `config`
+/// once for the entire batch.
+///
+/// If `unique` is `true`, then no two passwords in the batch are the same.
+/// In this case, the `count` is limited to the number of possible passwords
+/// that satisfy the `config`.
+///
+/// The letters of any given `alphabets` are added to the ASCII letters.
+#[napi(ts_return_type = "Array<string>")]
+pub fn generate_passwords<'env>(
+    env: &'env Env,
+    config: PasswordRequirements,
+    count: u32,
+    unique: Option<bool>,
+    alphabets: Option<Vec<Alphabet>>,
+) -> Result<Vec<JsString<'env>>> {
+    character_sets(alphabets)
+        .generate_passwords(
+            ::mk_pass::PasswordRequirements::from(&config),
+            count as usize,
+            unique.unwrap_or_default(),
+        )
+        .into_iter()
+        .map(|password| to_js_string(env, password))
+        .collect()
+}
+
+/// Calculate the theoretical entropy (in bits) of a password generated with the given `config`.
+///
+/// This function will invoke {@link validateRequirements} on the given `config`
+/// to ensure basic password requirements are met.
+///
+/// The entropy is the base 2 logarithm of the number of possible passwords
+/// that satisfy the `config`.
+/// The letters of any given `alphabets` are added to the ASCII letters.
+#[napi]
+pub fn password_entropy(config: PasswordRequirements, alphabets: Option<Vec<Alphabet>>) -> f64 {
+    character_sets(alphabets).password_entropy(::mk_pass::PasswordRequirements::from(&config))
+}
+
+/// Generate a pronounceable password given the constraints specified by `config`.
+///
+/// The letters alternate between consonant clusters (like `th`) and vowel clusters
+/// (like `ou`). The required decimal integers and special characters are inserted
+/// at random positions. The `config` is validated with
+/// {@link PasswordRequirements.allowRepeats} enabled.
+#[napi(ts_return_type = "string")]
+pub fn generate_pronounceable_password(
+    env: &Env,
+    config: PasswordRequirements,
+) -> Result<JsString<'_>> {
+    to_js_string(
+        env,
+        ::mk_pass::generate_pronounceable_password(::mk_pass::PasswordRequirements::from(&config)),
+    )
+}
+
+/// Calculate the entropy (in bits) of a pronounceable password generated with the given `config`.
+///
+/// Pronounceable passwords have much less entropy than other passwords of the same length.
 #[napi]
-pub fn generate_password(config: PasswordRequirements) -> String {
-    ::mk_pass::generate_password(::mk_pass::PasswordRequirements::from(&config))
+pub fn pronounceable_entropy(config: PasswordRequirements) -> f64 {
+    ::mk_pass::pronounceable_entropy(::mk_pass::PasswordRequirements::from(&config))
 }
 
 /// A class of factory methods to instantiate a {@link Samples.set} used
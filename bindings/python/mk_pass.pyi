@@ -1,17 +1,135 @@
-from typing import NamedTuple
+from collections.abc import Iterator
+from enum import Enum
+from typing import final
 
 def main() -> None: ...
 
-class PasswordRequirements(NamedTuple):
-    length: int = 16
-    decimal: int = 1
-    specials: int = 1
-    first_is_letter: bool = True
-    allow_repeats: bool = False
-
+@final
+class PasswordRequirements:
+    def __init__(
+        self,
+        length: int = 16,
+        decimal: int = 1,
+        specials: int = 1,
+        first_is_letter: bool = True,
+        allow_repeats: bool = False,
+        uppercase: int = 0,
+        lowercase: int = 0,
+        max_decimal: int | None = None,
+        max_specials: int | None = None,
+        max_uppercase: int | None = None,
+        max_lowercase: int | None = None,
+    ) -> None: ...
+    @property
+    def length(self) -> int: ...
+    @property
+    def decimal(self) -> int: ...
+    @property
+    def specials(self) -> int: ...
+    @property
+    def uppercase(self) -> int: ...
+    @property
+    def lowercase(self) -> int: ...
+    @property
+    def max_decimal(self) -> int | None: ...
+    @property
+    def max_specials(self) -> int | None: ...
+    @property
+    def max_uppercase(self) -> int | None: ...
+    @property
+    def max_lowercase(self) -> int | None: ...
+    @property
+    def first_is_letter(self) -> bool: ...
+    @property
+    def allow_repeats(self) -> bool: ...
+    def __eq__(self, other: object) -> bool: ...
     def validate(self) -> "PasswordRequirements": ...
+    @staticmethod
+    def preset(preset: "Preset") -> "PasswordRequirements": ...
+
+class Preset(Enum):
+    """A named set of password requirements derived from a published standard."""
+
+    #: NIST Special Publication 800-63B (revision 4), section 3.1.1.2.
+    NIST_800_63B = 0
+    #: PCI DSS (version 4.0.1), requirement 8.3.6.
+    PCI_DSS = 1
+    #: CIS Critical Security Controls (version 8), safeguard 5.2.
+    CIS = 2
+    #: OWASP Application Security Verification Standard (version 4.0.3).
+    OWASP_ASVS = 3
+
+class Alphabet(Enum):
+    """An extended alphabet of (non-ASCII) letters that can be used in a password."""
+
+    #: The letters of the Latin-1 Supplement block (like ``À``, ``ç``, and ``ß``).
+    LATIN1 = 0
+    #: The letters of the modern Greek alphabet (like ``Α``, ``β``, and ``ς``).
+    GREEK = 1
+    #: The letters of the basic Russian Cyrillic alphabet (like ``Ж``, ``ё``, and ``я``).
+    CYRILLIC = 2
+
+@final
+class SecretString:
+    """A secret string (like a generated password) that is wiped from memory when dropped.
+
+    The ``repr()`` (and ``str()``) of this object does not reveal the secret.
+    """
+
+    def expose_secret(self) -> str:
+        """Get the secret.
+
+        The returned ``str`` is a copy that is not wiped from memory.
+        """
+
+    def __len__(self) -> int: ...
+
+def generate_password(
+    config: PasswordRequirements, alphabets: list[Alphabet] | None = None
+) -> str: ...
+def generate(
+    *,
+    length: int = 16,
+    decimal: int = 1,
+    specials: int = 1,
+    first_is_letter: bool = True,
+    allow_repeats: bool = False,
+    uppercase: int = 0,
+    lowercase: int = 0,
+    max_decimal: int | None = None,
+    max_specials: int | None = None,
+    max_uppercase: int | None = None,
+    max_lowercase: int | None = None,
+    alphabets: list[Alphabet] | None = None,
+) -> str: ...
+def generate_secret_password(
+    config: PasswordRequirements, alphabets: list[Alphabet] | None = None
+) -> SecretString: ...
+def generate_passwords(
+    config: PasswordRequirements,
+    count: int,
+    unique: bool = False,
+    alphabets: list[Alphabet] | None = None,
+) -> list[str]: ...
+def generate_many(
+    config: PasswordRequirements,
+    n: int,
+    unique: bool = False,
+    alphabets: list[Alphabet] | None = None,
+) -> list[str]: ...
+@final
+class PasswordIterator(Iterator[str]):
+    def __iter__(self) -> "PasswordIterator": ...
+    def __next__(self) -> str: ...
 
-def generate_password(config: PasswordRequirements) -> str: ...
+def iter_passwords(
+    config: PasswordRequirements, alphabets: list[Alphabet] | None = None
+) -> PasswordIterator: ...
+def password_entropy(
+    config: PasswordRequirements, alphabets: list[Alphabet] | None = None
+) -> float: ...
+def generate_pronounceable_password(config: PasswordRequirements) -> str: ...
+def pronounceable_entropy(config: PasswordRequirements) -> float: ...
 
 #: The possible special characters used when generating a password.
 SPECIAL_CHARACTERS: list[str] = ...
@@ -3,7 +3,7 @@ use pyo3::prelude::*;
 /// A python package binding the mk-pass library written in rust.
 #[pymodule]
 pub mod mk_pass {
-    use pyo3::prelude::*;
+    use pyo3::{prelude::*, types::PyString};
 
     /// The function used as an entrypoint for the executable script.
     ///
@@ -11,14 +11,11 @@ pub mod mk_pass {
     /// they are parsed directly from `sys.argv`.
     #[pyfunction]
     pub fn main(py: Python) -> PyResult<()> {
-        use ::mk_pass::clap::Parser;
         let args = py
             .import("sys")?
             .getattr("argv")?
             .extract::<Vec<String>>()?;
-        let config = ::mk_pass::PasswordRequirements::parse_from(args);
-        let password = generate_password(&config.into());
-        println!("{password}");
+        ::mk_pass::Cli::parse_args(args).run();
         Ok(())
     }
 
@@ -35,6 +32,28 @@ pub mod mk_pass {
         /// How many special characters should the password contain?
         pub specials: u16,
 
+        /// At least how many uppercase letters should the password contain?
+        pub uppercase: u16,
+
+        /// At least how many lowercase letters should the password contain?
+        pub lowercase: u16,
+
+        /// At most how many numeric characters should the password contain?
+        ///
+        /// If not `None`, the actual count is picked randomly from `decimal` to this value.
+        pub max_decimal: Option<u16>,
+
+        /// At most how many special characters should the password contain?
+        ///
+        /// If not `None`, the actual count is picked randomly from `specials` to this value.
+        pub max_specials: Option<u16>,
+
+        /// At most how many uppercase letters should the password contain?
+        pub max_uppercase: Option<u16>,
+
+        /// At most how many lowercase letters should the password contain?
+        pub max_lowercase: Option<u16>,
+
         /// Should the first character always be a letter?
         pub first_is_letter: bool,
 
@@ -46,19 +65,32 @@ pub mod mk_pass {
     impl PasswordRequirements {
         #[new]
         #[pyo3(
-        signature = (length = 16, decimal=1, specials=1, first_is_letter = true, allow_repeats = false)
+        signature = (length = 16, decimal=1, specials=1, first_is_letter = true, allow_repeats = false, uppercase = 0, lowercase = 0, max_decimal = None, max_specials = None, max_uppercase = None, max_lowercase = None)
     )]
+        #[allow(clippy::too_many_arguments)]
         pub fn new(
             length: Option<i32>,
             decimal: Option<i32>,
             specials: Option<i32>,
             first_is_letter: Option<bool>,
             allow_repeats: Option<bool>,
+            uppercase: Option<i32>,
+            lowercase: Option<i32>,
+            max_decimal: Option<i32>,
+            max_specials: Option<i32>,
+            max_uppercase: Option<i32>,
+            max_lowercase: Option<i32>,
         ) -> Self {
             Self {
                 length: length.unwrap_or(16) as u16,
                 decimal: decimal.unwrap_or(1) as u16,
                 specials: specials.unwrap_or(1) as u16,
+                uppercase: uppercase.unwrap_or_default() as u16,
+                lowercase: lowercase.unwrap_or_default() as u16,
+                max_decimal: max_decimal.map(|v| v as u16),
+                max_specials: max_specials.map(|v| v as u16),
+                max_uppercase: max_uppercase.map(|v| v as u16),
+                max_lowercase: max_lowercase.map(|v| v as u16),
                 first_is_letter: first_is_letter.unwrap_or(true),
                 allow_repeats: allow_repeats.unwrap_or_default(),
             }
@@ -68,6 +100,14 @@ pub mod mk_pass {
             format!("{self:?}")
         }
 
+        /// The password requirements of the given `preset`.
+        ///
+        /// See [`Preset`][mk_pass.Preset] for the standards from which each preset is derived.
+        #[staticmethod]
+        pub fn preset(preset: Preset) -> Self {
+            ::mk_pass::PasswordRequirements::preset(preset.into()).into()
+        }
+
         /// Validates the instance's values.
         ///
         /// This returns a mutated clone of the instance where the values satisfy
@@ -76,22 +116,34 @@ pub mod mk_pass {
         /// The phrase "sane minimum requirements" implies
         ///
         /// 1. `length` is not less than 10
-        /// 2. To avoid repetitions, `length` is not more than
-        ///     - 52 if only letters (no decimal integers or special characters) are used
-        ///     - 62 if only letters and decimal integers are used
-        ///     - 68 if only letters and special characters are used
-        ///     - 78 if letters, decimal integers, and special characters are used
+        /// 2. To avoid repetitions, `length` is not more than the 52 letters plus
+        ///     the (maximum) counts of decimal integers and special characters. For example,
+        ///     - 54 if the password contains 1 decimal integer and 1 special character (the default)
+        ///     - 78 if the password contains 10 decimal integers and 16 special characters
         ///     - 65535 if repeated characters are allowed
-        /// 3. `specials` character count does not overrule the required number of
-        ///     - letters (2; 1 uppercase and 1 lowercase)
+        /// 3. `uppercase` and `lowercase` letter counts do not overrule the required number of
+        ///     - lowercase letters (1; if `uppercase` is the overruling count)
+        ///     - decimal integers (1; if `decimal` is specified as non-zero value)
+        ///     - special characters (1; if `specials` is specified as non-zero value)
+        /// 4. `specials` character count does not overrule the required number of
+        ///     - letters (at least 2; 1 uppercase and 1 lowercase or
+        ///       the specified `uppercase` and `lowercase` counts)
         ///     - decimal integers (if `decimal` is specified as non-zero value)
-        /// 4. `decimal` character count does not overrule the required number of
-        ///     - letters (2; 1 uppercase and 1 lowercase)
+        /// 5. `decimal` character count does not overrule the required number of
+        ///     - letters (at least 2; 1 uppercase and 1 lowercase or
+        ///       the specified `uppercase` and `lowercase` counts)
         ///     - special characters (if `specials` is specified as non-zero value)
+        /// 6. To avoid repetitions, `decimal` is not more than 10, `specials` is not more than 16,
+        ///     and `uppercase` and `lowercase` are each not more than 26.
+        ///     Consequently, `length` is shortened if there are not enough letters (52)
+        ///     to fill the remainder.
+        /// 7. Each maximum count (like `max_decimal`) is not less than the respective minimum count.
+        ///     Consequently, `length` is shortened if the maximum counts cannot fill it.
         ///
         /// Note:
         ///     If this function finds a conflict between the specified number of
         ///     ``specials`` characters and ``decimal``, then decimal integers takes precedence.
+        ///     Likewise, ``uppercase`` letters take precedence over ``lowercase`` letters.
         ///
         ///     For example:
         ///
@@ -101,7 +153,7 @@ pub mod mk_pass {
         ///     >>> req
         ///     PasswordRequirements { length: 16, decimal: 16, specials: 16, first_is_letter: true }
         ///     >>> req.validate()
-        ///     PasswordRequirements { length: 16, decimal: 13, specials: 1, first_is_letter: true }
+        ///     PasswordRequirements { length: 16, decimal: 10, specials: 1, first_is_letter: true }
         ///     ```
         pub fn validate(&self) -> Self {
             let config: ::mk_pass::PasswordRequirements = self.into();
@@ -109,12 +161,62 @@ pub mod mk_pass {
         }
     }
 
+    /// A named set of password requirements derived from a published standard.
+    ///
+    /// Standards that forbid composition rules are encoded with a minimum count of `0`
+    /// and no effective maximum count for decimal integers and special characters.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[pyclass(module = "mk_pass", eq, eq_int, frozen, from_py_object)]
+    pub enum Preset {
+        /// NIST Special Publication 800-63B (revision 4), section 3.1.1.2.
+        ///
+        /// At least 15 characters and no composition rules.
+        #[pyo3(name = "NIST_800_63B")]
+        Nist80063b,
+
+        /// PCI DSS (version 4.0.1), requirement 8.3.6.
+        ///
+        /// At least 12 characters, including numeric and alphabetic characters.
+        #[pyo3(name = "PCI_DSS")]
+        PciDss,
+
+        /// CIS Critical Security Controls (version 8), safeguard 5.2.
+        ///
+        /// At least 14 characters (for accounts without multi-factor authentication).
+        #[pyo3(name = "CIS")]
+        Cis,
+
+        /// OWASP Application Security Verification Standard (version 4.0.3),
+        /// requirements 2.1.1 and 2.1.9.
+        ///
+        /// At least 12 characters and no composition rules.
+        #[pyo3(name = "OWASP_ASVS")]
+        OwaspAsvs,
+    }
+
+    impl From<Preset> for ::mk_pass::Preset {
+        fn from(value: Preset) -> Self {
+            match value {
+                Preset::Nist80063b => Self::Nist80063b,
+                Preset::PciDss => Self::PciDss,
+                Preset::Cis => Self::Cis,
+                Preset::OwaspAsvs => Self::OwaspAsvs,
+            }
+        }
+    }
+
     impl From<::mk_pass::PasswordRequirements> for PasswordRequirements {
         fn from(value: ::mk_pass::PasswordRequirements) -> Self {
             Self {
                 length: value.length,
                 decimal: value.decimal,
                 specials: value.specials,
+                uppercase: value.uppercase,
+                lowercase: value.lowercase,
+                max_decimal: value.max_decimal,
+                max_specials: value.max_specials,
+                max_uppercase: value.max_uppercase,
+                max_lowercase: value.max_lowercase,
                 first_is_letter: value.first_is_letter,
                 allow_repeats: value.allow_repeats,
             }
@@ -127,20 +229,316 @@ pub mod mk_pass {
                 length: value.length,
                 decimal: value.decimal,
                 specials: value.specials,
+                uppercase: value.uppercase,
+                lowercase: value.lowercase,
+                max_decimal: value.max_decimal,
+                max_specials: value.max_specials,
+                max_uppercase: value.max_uppercase,
+                max_lowercase: value.max_lowercase,
                 first_is_letter: value.first_is_letter,
                 allow_repeats: value.allow_repeats,
             }
         }
     }
 
+    /// An extended alphabet of (non-ASCII) letters that can be used in a password.
+    ///
+    /// Each alphabet only includes precomposed letters,
+    /// so passwords are always in Unicode Normalization Form C (NFC).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[pyclass(module = "mk_pass", eq, eq_int, frozen, from_py_object)]
+    pub enum Alphabet {
+        /// The letters of the Latin-1 Supplement block (like `À`, `ç`, and `ß`).
+        #[pyo3(name = "LATIN1")]
+        Latin1,
+
+        /// The letters of the modern Greek alphabet (like `Α`, `β`, and `ς`).
+        #[pyo3(name = "GREEK")]
+        Greek,
+
+        /// The letters of the basic Russian Cyrillic alphabet (like `Ж`, `ё`, and `я`).
+        #[pyo3(name = "CYRILLIC")]
+        Cyrillic,
+    }
+
+    impl From<Alphabet> for ::mk_pass::Alphabet {
+        fn from(value: Alphabet) -> Self {
+            match value {
+                Alphabet::Latin1 => Self::Latin1,
+                Alphabet::Greek => Self::Greek,
+                Alphabet::Cyrillic => Self::Cyrillic,
+            }
+        }
+    }
+
+    /// The character sets with the letters of the given `alphabets` (if any).
+    fn character_sets(alphabets: Option<Vec<Alphabet>>) -> ::mk_pass::CharacterSets {
+        alphabets
+            .unwrap_or_default()
+            .into_iter()
+            .fold(::mk_pass::CharacterSets::default(), |sets, alphabet| {
+                sets.with_alphabet(alphabet.into())
+            })
+    }
+
+    /// A secret string (like a generated password) that is wiped from memory when dropped.
+    ///
+    /// The `repr()` (and `str()`) of this object does not reveal the secret.
+    /// Use [`expose_secret()`][mk_pass.SecretString.expose_secret] to access the secret.
+    #[pyclass(module = "mk_pass", frozen)]
+    pub struct SecretString(::mk_pass::SecretString);
+
+    #[pymethods]
+    impl SecretString {
+        /// Get the secret.
+        ///
+        /// The returned `str` is a copy that is not wiped from memory.
+        pub fn expose_secret(&self) -> &str {
+            self.0.expose_secret()
+        }
+
+        pub fn __repr__(&self) -> String {
+            format!("{:?}", self.0)
+        }
+
+        /// The number of characters (Unicode code points) in the secret.
+        pub fn __len__(&self) -> usize {
+            self.0.expose_secret().chars().count()
+        }
+    }
+
+    /// Copy the given `secret` into a Python `str`, then wipe the `secret` from memory.
+    fn into_py_string(py: Python<'_>, secret: String) -> Bound<'_, PyString> {
+        let secret = ::mk_pass::SecretString::from(secret);
+        PyString::new(py, secret.expose_secret())
+    }
+
+    /// Generate a password given the constraints specified by `config`.
+    ///
+    /// This function will invoke
+    /// [`PasswordRequirements.validate()`][mk_pass.PasswordRequirements.validate]
+    /// to ensure basic password requirements are met.
+    ///
+    /// The letters of any given `alphabets` are added to the ASCII letters.
+    /// The password's length is counted in characters (Unicode code points).
+    #[pyfunction]
+    #[pyo3(signature = (config, alphabets = None))]
+    pub fn generate_password<'py>(
+        py: Python<'py>,
+        config: &PasswordRequirements,
+        alphabets: Option<Vec<Alphabet>>,
+    ) -> Bound<'py, PyString> {
+        into_py_string(
+            py,
+            character_sets(alphabets).generate_password(config.into()),
+        )
+    }
+
+    /// Generate a password given the constraints specified as keyword arguments.
+    ///
+    /// The keyword arguments (except `alphabets`) are the same as those of
+    /// [`PasswordRequirements`][mk_pass.PasswordRequirements], so
+    ///
+    /// ```python
+    /// >>> password = generate(length=20, specials=0)
+    /// ```
+    ///
+    /// is the same as
+    ///
+    /// ```python
+    /// >>> password = generate_password(PasswordRequirements(length=20, specials=0))
+    /// ```
+    #[pyfunction]
+    #[pyo3(
+        signature = (*, length = 16, decimal = 1, specials = 1, first_is_letter = true, allow_repeats = false, uppercase = 0, lowercase = 0, max_decimal = None, max_specials = None, max_uppercase = None, max_lowercase = None, alphabets = None)
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate(
+        py: Python<'_>,
+        length: Option<i32>,
+        decimal: Option<i32>,
+        specials: Option<i32>,
+        first_is_letter: Option<bool>,
+        allow_repeats: Option<bool>,
+        uppercase: Option<i32>,
+        lowercase: Option<i32>,
+        max_decimal: Option<i32>,
+        max_specials: Option<i32>,
+        max_uppercase: Option<i32>,
+        max_lowercase: Option<i32>,
+        alphabets: Option<Vec<Alphabet>>,
+    ) -> Bound<'_, PyString> {
+        let config = PasswordRequirements::new(
+            length,
+            decimal,
+            specials,
+            first_is_letter,
+            allow_repeats,
+            uppercase,
+            lowercase,
+            max_decimal,
+            max_specials,
+            max_uppercase,
+            max_lowercase,
+        );
+        generate_password(py, &config, alphabets)
+    }
+
     /// Generate a password given the constraints specified by `config`.
     ///
+    /// This behaves like [`generate_password()`][mk_pass.generate_password], but the
+    /// password is returned as a [`SecretString`][mk_pass.SecretString] that is
+    /// wiped from memory when it is garbage collected.
+    #[pyfunction]
+    #[pyo3(signature = (config, alphabets = None))]
+    pub fn generate_secret_password(
+        config: &PasswordRequirements,
+        alphabets: Option<Vec<Alphabet>>,
+    ) -> SecretString {
+        SecretString(character_sets(alphabets).generate_secret_password(config.into()))
+    }
+
+    /// Generate a `count` of passwords given the constraints specified by `config`.
+    ///
+    /// This function will invoke
+    /// [`PasswordRequirements.validate()`][mk_pass.PasswordRequirements.validate]
+    /// once for the entire batch.
+    ///
+    /// If `unique` is `True`, then no two passwords in the batch are the same.
+    /// In this case, the `count` is limited to the number of possible passwords
+    /// that satisfy the `config`.
+    ///
+    /// The letters of any given `alphabets` are added to the ASCII letters.
+    ///
+    /// This is the same as [`generate_many()`][mk_pass.generate_many].
+    #[pyfunction]
+    #[pyo3(signature = (config, count, unique = false, alphabets = None))]
+    pub fn generate_passwords<'py>(
+        py: Python<'py>,
+        config: &PasswordRequirements,
+        count: usize,
+        unique: bool,
+        alphabets: Option<Vec<Alphabet>>,
+    ) -> Vec<Bound<'py, PyString>> {
+        generate_many(py, config, count, unique, alphabets)
+    }
+
+    /// Generate `n` passwords given the constraints specified by `config`.
+    ///
+    /// The GIL is released while the passwords are generated,
+    /// so other Python threads can run in the meantime.
+    ///
+    /// If `unique` is `True`, then no two passwords in the batch are the same.
+    /// In this case, `n` is limited to the number of possible passwords
+    /// that satisfy the `config`.
+    ///
+    /// The letters of any given `alphabets` are added to the ASCII letters.
+    #[pyfunction]
+    #[pyo3(signature = (config, n, unique = false, alphabets = None))]
+    pub fn generate_many<'py>(
+        py: Python<'py>,
+        config: &PasswordRequirements,
+        n: usize,
+        unique: bool,
+        alphabets: Option<Vec<Alphabet>>,
+    ) -> Vec<Bound<'py, PyString>> {
+        let sets = character_sets(alphabets);
+        let config = config.into();
+        py.detach(move || sets.generate_passwords(config, n, unique))
+            .into_iter()
+            .map(|password| into_py_string(py, password))
+            .collect()
+    }
+
+    /// An endless iterator of passwords that satisfy the same requirements.
+    ///
+    /// Use [`iter_passwords()`][mk_pass.iter_passwords] to create this iterator.
+    /// Each password is generated when it is requested, so there is no limit
+    /// to how many passwords can be taken from this iterator.
+    /// Unlike [`generate_many()`][mk_pass.generate_many], the passwords are not
+    /// guaranteed to be unique.
+    #[pyclass(module = "mk_pass", frozen)]
+    pub struct PasswordIterator {
+        sets: ::mk_pass::CharacterSets,
+        config: ::mk_pass::PasswordRequirements,
+    }
+
+    #[pymethods]
+    impl PasswordIterator {
+        pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+            slf
+        }
+
+        pub fn __next__<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+            into_py_string(py, self.sets.generate_password(self.config))
+        }
+    }
+
+    /// Create an endless iterator of passwords given the constraints specified by `config`.
+    ///
+    /// The `config` is validated once with
+    /// [`PasswordRequirements.validate()`][mk_pass.PasswordRequirements.validate].
+    /// The letters of any given `alphabets` are added to the ASCII letters.
+    ///
+    /// ```python
+    /// >>> from itertools import islice
+    /// >>> passwords = list(islice(iter_passwords(PasswordRequirements()), 5))
+    /// ```
+    #[pyfunction]
+    #[pyo3(signature = (config, alphabets = None))]
+    pub fn iter_passwords(
+        config: &PasswordRequirements,
+        alphabets: Option<Vec<Alphabet>>,
+    ) -> PasswordIterator {
+        let config: ::mk_pass::PasswordRequirements = config.into();
+        PasswordIterator {
+            sets: character_sets(alphabets),
+            config: config.validate(),
+        }
+    }
+
+    /// Calculate the theoretical entropy (in bits) of a password generated with the given `config`.
+    ///
     /// This function will invoke
     /// [`PasswordRequirements.validate()`][mk_pass.PasswordRequirements.validate]
     /// to ensure basic password requirements are met.
+    ///
+    /// The entropy is the base 2 logarithm of the number of possible passwords
+    /// that satisfy the `config`.
+    /// The letters of any given `alphabets` are added to the ASCII letters.
+    #[pyfunction]
+    #[pyo3(signature = (config, alphabets = None))]
+    pub fn password_entropy(
+        config: &PasswordRequirements,
+        alphabets: Option<Vec<Alphabet>>,
+    ) -> f64 {
+        character_sets(alphabets).password_entropy(config.into())
+    }
+
+    /// Generate a pronounceable password given the constraints specified by `config`.
+    ///
+    /// The letters alternate between consonant clusters (like `th`) and
+    /// vowel clusters (like `ou`). The required decimal integers and special
+    /// characters are inserted at random positions.
+    /// The `config` is validated with
+    /// [`allow_repeats`][mk_pass.PasswordRequirements.allow_repeats] enabled.
+    #[pyfunction]
+    pub fn generate_pronounceable_password<'py>(
+        py: Python<'py>,
+        config: &PasswordRequirements,
+    ) -> Bound<'py, PyString> {
+        into_py_string(
+            py,
+            ::mk_pass::generate_pronounceable_password(config.into()),
+        )
+    }
+
+    /// Calculate the entropy (in bits) of a pronounceable password generated with the given `config`.
+    ///
+    /// Pronounceable passwords have much less entropy than other passwords of the same length.
     #[pyfunction]
-    pub fn generate_password(config: &PasswordRequirements) -> String {
-        ::mk_pass::generate_password(config.into())
+    pub fn pronounceable_entropy(config: &PasswordRequirements) -> f64 {
+        ::mk_pass::pronounceable_entropy(config.into())
     }
 
     #[pymodule_export]
@@ -1,8 +1,21 @@
 import sys
+import threading
+import unicodedata
+from itertools import islice
 import pytest
 from mk_pass import (
+    Alphabet,
+    generate,
+    generate_many,
     generate_password,
+    generate_passwords,
+    generate_pronounceable_password,
+    generate_secret_password,
+    iter_passwords,
+    password_entropy,
+    pronounceable_entropy,
     PasswordRequirements,
+    Preset,
     main,
     LOWERCASE,
     UPPERCASE,
@@ -33,19 +46,147 @@ def test_password() -> None:
     assert_password_is_expected(password, config)
 
 
+def test_batch() -> None:
+    config = PasswordRequirements()  # default
+    passwords = generate_passwords(config, 10, unique=True)
+    assert len(passwords) == 10
+    assert len(set(passwords)) == 10
+    for password in passwords:
+        assert_password_is_expected(password, config)
+
+
+def test_keywords() -> None:
+    password = generate(length=20, specials=0, decimal=3)
+    assert_password_is_expected(
+        password, PasswordRequirements(length=20, specials=0, decimal=3)
+    )
+    assert_password_is_expected(generate(), PasswordRequirements())
+    assert len(generate(length=30, alphabets=[Alphabet.GREEK])) == 30
+
+    # without repeats, no character is used twice
+    password = generate(length=20, specials=0, allow_repeats=False)
+    assert len(password) == 20
+    assert len(set(password)) == len(password)
+    # repeats are allowed, but not guaranteed
+    assert len(generate(length=20, specials=0, allow_repeats=True)) == 20
+
+
+def test_generate_many() -> None:
+    config = PasswordRequirements()  # default
+    results: list[list[str]] = []
+    threads = [
+        threading.Thread(
+            target=lambda: results.append(generate_many(config, 100, unique=True))
+        )
+        for _ in range(4)
+    ]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert len(results) == 4
+    for passwords in results:
+        assert len(set(passwords)) == 100
+        for password in passwords:
+            assert_password_is_expected(password, config)
+
+
+def test_iterator() -> None:
+    config = PasswordRequirements(length=12)
+    passwords = iter_passwords(config)
+    assert iter(passwords) is passwords
+    batch = list(islice(passwords, 50))
+    assert len(batch) == 50
+    for password in batch:
+        assert_password_is_expected(password, config)
+    # the iterator never stops
+    assert len(next(passwords)) == 12
+
+
 def test_repeats() -> None:
     config = PasswordRequirements(decimal=18, specials=0, length=20, allow_repeats=True)
     password = generate_password(config)
     assert_password_is_expected(password, config)
 
 
+def test_letter_counts() -> None:
+    config = PasswordRequirements(uppercase=10, lowercase=2)
+    password = generate_password(config)
+    assert_password_is_expected(password, config)
+    assert sum([1 for x in password if x in UPPERCASE]) >= 10
+    assert sum([1 for x in password if x in LOWERCASE]) >= 2
+
+
+def test_count_ranges() -> None:
+    config = PasswordRequirements(decimal=1, max_decimal=4, specials=0, max_specials=3)
+    assert config.validate() == config
+    for _ in range(20):
+        password = generate_password(config)
+        assert len(password) == config.length
+        assert 1 <= sum([1 for x in password if x in DECIMAL]) <= 4
+        assert sum([1 for x in password if x in SPECIAL_CHARACTERS]) <= 3
+
+
 def test_config() -> None:
     config = PasswordRequirements(decimal=15, specials=15)
-    expected = PasswordRequirements(decimal=13, specials=1)
+    expected = PasswordRequirements(decimal=10, specials=1)
     validated = config.validate()
     assert validated == expected
 
 
+def test_entropy() -> None:
+    config = PasswordRequirements()  # default
+    assert round(password_entropy(config), 2) == 90.03
+    repeats = PasswordRequirements(allow_repeats=True)
+    assert password_entropy(repeats) > password_entropy(config)
+
+
+def test_preset() -> None:
+    config = PasswordRequirements.preset(Preset.PCI_DSS)
+    assert config.length == 12
+    assert config.decimal == 1
+    assert config.validate() == config
+    password = generate_password(config)
+    assert len(password) == 12
+    assert sum([1 for x in password if x in DECIMAL]) >= 1
+    assert PasswordRequirements.preset(Preset.NIST_800_63B).length == 15
+
+
+def test_alphabets() -> None:
+    config = PasswordRequirements(length=40)
+    alphabets = [Alphabet.GREEK, Alphabet.CYRILLIC]
+    password = generate_password(config, alphabets=alphabets)
+    assert len(password) == 40
+    assert len(password.encode("utf-8")) > 40
+    assert unicodedata.normalize("NFC", password) == password
+    assert any(not x.isascii() for x in password)
+    passwords = generate_passwords(config, 3, alphabets=[Alphabet.LATIN1])
+    assert all(len(p) == 40 for p in passwords)
+    assert password_entropy(config, alphabets) > password_entropy(config)
+
+
+def test_pronounceable() -> None:
+    config = PasswordRequirements(length=20, decimal=2)
+    password = generate_pronounceable_password(config)
+    # pronounceable passwords may repeat characters (like vowels)
+    assert password[0] in LOWERCASE or password[0] in UPPERCASE
+    assert len(password) == config.length
+    assert sum([1 for x in password if x in DECIMAL]) == config.decimal
+    assert sum([1 for x in password if x in SPECIAL_CHARACTERS]) == config.specials
+    assert any(x in UPPERCASE for x in password)
+    assert 0 < pronounceable_entropy(config) < password_entropy(config)
+
+
+def test_secret_password() -> None:
+    config = PasswordRequirements()
+    secret = generate_secret_password(config)
+    password = secret.expose_secret()
+    assert_password_is_expected(password, config)
+    assert len(secret) == config.length
+    assert password not in repr(secret)
+    assert password not in str(secret)
+
+
 def test_main(monkeypatch: pytest.MonkeyPatch, capfd: pytest.CaptureFixture) -> None:
     monkeypatch.setattr(sys, "argv", ["mk-pass"])
     main()
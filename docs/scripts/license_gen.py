@@ -17,6 +17,12 @@ INTRO = """# Third-party Licenses
 The key icon used in this project was sourced from the
 [Heroicons](https://heroicons.com/) project (licensed under [MIT]).
 
+## Wordlist
+
+The default wordlist used to generate passphrases is the
+[EFF's large wordlist](https://www.eff.org/dice) (licensed under
+[CC BY 3.0 US](https://creativecommons.org/licenses/by/3.0/us/)).
+
 ## Software dependencies
 
 """
@@ -3,15 +3,27 @@
 use std::collections::HashMap;
 
 use mk_pass::{
-    PasswordRequirements,
-    clap::{ArgAction, CommandFactory},
+    Cli,
+    clap::{ArgAction, Command, CommandFactory},
 };
 use pyo3::{exceptions::PyValueError, prelude::*};
 
-#[pyfunction]
-fn generate_cli_doc(metadata: HashMap<String, HashMap<String, Py<PyAny>>>) -> PyResult<String> {
-    let mut out = String::new();
-    let mut command = PasswordRequirements::command();
+type Metadata = HashMap<String, HashMap<String, Py<PyAny>>>;
+
+/// Write the version badge and flags (if any) about an option or subcommand.
+fn write_metadata(out: &mut String, metadata: &Metadata, key: &str) {
+    if let Some(map) = metadata.get(key) {
+        if let Some(val) = map.get("minimum-version") {
+            out.push_str(format!("<!-- md:version {val} -->\n").as_str());
+        }
+        if map.contains_key("experimental") {
+            out.push_str("<!-- md:flag experimental -->\n");
+        }
+    }
+}
+
+/// Write the usage of the given `command`.
+fn write_usage(out: &mut String, command: &mut Command) {
     out.push_str(
         format!(
             "```text title=\"Usage\"\n{}\n```\n",
@@ -22,30 +34,38 @@ fn generate_cli_doc(metadata: HashMap<String, HashMap<String, Py<PyAny>>>) -> Py
         )
         .as_str(),
     );
+}
 
-    out.push_str("\n## Options\n");
+/// Write the documentation about the options of the given `command`.
+///
+/// The `prefix` is used to look up the `metadata` about options that belong to a subcommand.
+fn write_options(
+    out: &mut String,
+    command: &Command,
+    metadata: &Metadata,
+    heading: &str,
+    prefix: &str,
+) -> PyResult<()> {
     for arg in command.get_arguments() {
         let arg_id = arg.get_id().as_str();
-        let long_name = arg.get_long().ok_or(PyValueError::new_err(format!(
-            "Failed to get long name of argument with id {arg_id}",
-        )))?;
-        out.push_str(
-            format!(
-                "\n### `-{}, --{}`\n\n",
-                arg.get_short().ok_or(PyValueError::new_err(format!(
-                    "Failed to get short name for argument with id {arg_id}"
-                )))?,
-                long_name
-            )
-            .as_str(),
-        );
-        if let Some(map) = metadata.get(long_name) {
-            if let Some(val) = map.get("minimum-version") {
-                out.push_str(format!("<!-- md:version {val} -->\n").as_str());
-            }
-            if map.contains_key("experimental") {
-                out.push_str("<!-- md:flag experimental -->\n");
-            }
+        if arg.is_positional() {
+            out.push_str(format!("\n{heading} `{}`\n\n", arg_id.to_uppercase()).as_str());
+            write_metadata(out, metadata, format!("{prefix}{arg_id}").as_str());
+        } else {
+            let long_name = arg.get_long().ok_or(PyValueError::new_err(format!(
+                "Failed to get long name of argument with id {arg_id}",
+            )))?;
+            out.push_str(
+                format!(
+                    "\n{heading} `-{}, --{}`\n\n",
+                    arg.get_short().ok_or(PyValueError::new_err(format!(
+                        "Failed to get short name for argument with id {arg_id}"
+                    )))?,
+                    long_name
+                )
+                .as_str(),
+            );
+            write_metadata(out, metadata, format!("{prefix}{long_name}").as_str());
         }
         match arg.get_action() {
             ArgAction::SetTrue | ArgAction::SetFalse | ArgAction::Help | ArgAction::Version => {
@@ -53,16 +73,17 @@ fn generate_cli_doc(metadata: HashMap<String, HashMap<String, Py<PyAny>>>) -> Py
             }
             _ => {
                 let default = arg.get_default_values();
-                if let Some(default_value) = default.first() {
-                    out.push_str(
-                        format!(
-                            "<!-- md:default {} -->\n\n",
-                            default_value.to_string_lossy()
-                        )
-                        .as_str(),
-                    );
-                } else {
-                    out.push('\n');
+                match default.first() {
+                    Some(default_value) if !arg.is_hide_default_value_set() => {
+                        out.push_str(
+                            format!(
+                                "<!-- md:default {} -->\n\n",
+                                default_value.to_string_lossy()
+                            )
+                            .as_str(),
+                        );
+                    }
+                    _ => out.push('\n'),
                 }
             }
         }
@@ -70,6 +91,41 @@ fn generate_cli_doc(metadata: HashMap<String, HashMap<String, Py<PyAny>>>) -> Py
             out.push_str(format!("{}\n", help.to_string().trim()).as_str());
         }
     }
+    Ok(())
+}
+
+#[pyfunction]
+fn generate_cli_doc(metadata: Metadata) -> PyResult<String> {
+    let mut out = String::new();
+    let mut command = Cli::command();
+    write_usage(&mut out, &mut command);
+
+    out.push_str("\n## Options\n");
+    write_options(&mut out, &command, &metadata, "###", "")?;
+
+    let mut subcommands = command
+        .get_subcommands()
+        .filter(|cmd| cmd.get_name() != "help")
+        .peekable();
+    if subcommands.peek().is_some() {
+        out.push_str("\n## Commands\n");
+    }
+    for subcommand in subcommands {
+        let name = subcommand.get_name();
+        out.push_str(format!("\n### `{name}`\n\n").as_str());
+        write_metadata(&mut out, &metadata, name);
+        if let Some(about) = subcommand.get_long_about().or(subcommand.get_about()) {
+            out.push_str(format!("\n{}\n\n", about.to_string().trim()).as_str());
+        }
+        write_usage(&mut out, &mut subcommand.clone());
+        write_options(
+            &mut out,
+            subcommand,
+            &metadata,
+            "####",
+            format!("{name}.").as_str(),
+        )?;
+    }
     Ok(out)
 }
 
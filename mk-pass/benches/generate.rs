@@ -0,0 +1,84 @@
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use mk_pass::{PasswordRequirements, generate_password};
+
+/// The lengths (in characters) of the generated secrets, up to the maximum length.
+const LENGTHS: [u16; 5] = [16, 1024, 4096, 16384, u16::MAX];
+
+/// The lengths (in characters) of the secrets with multiple ranges of counts, from 4 to 64 KiB.
+const LONG_LENGTHS: [u16; 3] = [4096, 16384, u16::MAX];
+
+fn with_repeats(c: &mut Criterion) {
+    let mut group = c.benchmark_group("allow_repeats");
+    for length in LENGTHS {
+        let config = PasswordRequirements {
+            length,
+            allow_repeats: true,
+            ..Default::default()
+        };
+        group.throughput(Throughput::Elements(length as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(length), &config, |b, config| {
+            b.iter(|| generate_password(black_box(*config)))
+        });
+    }
+    group.finish();
+}
+
+fn with_count_ranges(c: &mut Criterion) {
+    let mut group = c.benchmark_group("count_ranges");
+    for length in LENGTHS {
+        let config = PasswordRequirements {
+            length,
+            max_decimal: Some(length / 4),
+            allow_repeats: true,
+            ..Default::default()
+        };
+        group.throughput(Throughput::Elements(length as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(length), &config, |b, config| {
+            b.iter(|| generate_password(black_box(*config)))
+        });
+    }
+    group.finish();
+}
+
+fn with_multiple_count_ranges(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multiple_count_ranges");
+    group.sample_size(10);
+    for length in LONG_LENGTHS {
+        let ranges = [("decimal_specials", None), ("all_kinds", Some(u16::MAX))];
+        for (name, max_letters) in ranges {
+            let config = PasswordRequirements {
+                length,
+                decimal: 0,
+                max_decimal: Some(u16::MAX),
+                specials: 0,
+                max_specials: Some(u16::MAX),
+                max_uppercase: max_letters,
+                max_lowercase: max_letters,
+                allow_repeats: true,
+                ..Default::default()
+            };
+            group.throughput(Throughput::Elements(length as u64));
+            group.bench_with_input(BenchmarkId::new(name, length), &config, |b, config| {
+                b.iter(|| generate_password(black_box(*config)))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn without_repeats(c: &mut Criterion) {
+    c.bench_function("default", |b| {
+        b.iter(|| generate_password(black_box(PasswordRequirements::default())))
+    });
+}
+
+criterion_group!(
+    benches,
+    with_repeats,
+    with_count_ranges,
+    with_multiple_count_ranges,
+    without_repeats
+);
+criterion_main!(benches);
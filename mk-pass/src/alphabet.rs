@@ -0,0 +1,73 @@
+use std::{fmt::Display, str::FromStr};
+
+/// An extended alphabet of (non-ASCII) letters that can be added to the [`CharacterSets`](crate::CharacterSets).
+///
+/// Each alphabet only includes precomposed letters, so passwords are always in
+/// Unicode Normalization Form C (NFC).
+/// Note that these letters take more than 1 byte when encoded as UTF-8, and
+/// some of them look like ASCII letters (for example, Cyrillic `А` and Latin `A`).
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alphabet {
+    /// The letters of the Latin-1 Supplement block (like `À`, `ç`, and `ß`).
+    Latin1,
+
+    /// The letters of the modern Greek alphabet (like `Α`, `β`, and `ς`).
+    Greek,
+
+    /// The letters of the basic Russian Cyrillic alphabet (like `Ж`, `ё`, and `я`).
+    Cyrillic,
+}
+
+impl Alphabet {
+    /// All the available alphabets.
+    pub const ALL: [Alphabet; 3] = [Self::Latin1, Self::Greek, Self::Cyrillic];
+
+    /// The name of this alphabet (as accepted by the `--alphabet` CLI option).
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Latin1 => "latin1",
+            Self::Greek => "greek",
+            Self::Cyrillic => "cyrillic",
+        }
+    }
+
+    /// The uppercase letters of this alphabet.
+    pub fn uppercase(&self) -> Vec<char> {
+        match self {
+            Self::Latin1 => ('À'..='Þ').filter(|ch| *ch != '×').collect(),
+            // U+03A2 is unassigned
+            Self::Greek => ('Α'..='Ω').filter(|ch| *ch != '\u{3a2}').collect(),
+            Self::Cyrillic => std::iter::once('Ё').chain('А'..='Я').collect(),
+        }
+    }
+
+    /// The lowercase letters of this alphabet.
+    pub fn lowercase(&self) -> Vec<char> {
+        match self {
+            Self::Latin1 => ('ß'..='ÿ').filter(|ch| *ch != '÷').collect(),
+            Self::Greek => ('α'..='ω').collect(),
+            Self::Cyrillic => std::iter::once('ё').chain('а'..='я').collect(),
+        }
+    }
+}
+
+impl Display for Alphabet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Alphabet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|alphabet| alphabet.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names = Self::ALL.map(|alphabet| alphabet.name()).join(", ");
+                format!("Unknown alphabet '{s}' (available alphabets are: {names})")
+            })
+    }
+}
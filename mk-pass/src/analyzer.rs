@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use crate::{CharacterSets, PasswordRequirements};
+
+/// A report about the contents of an existing password.
+///
+/// See [`analyze_password()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordAnalysis {
+    /// The number of characters in the password.
+    pub length: usize,
+
+    /// The number of uppercase letters in the password.
+    pub uppercase: usize,
+
+    /// The number of lowercase letters in the password.
+    pub lowercase: usize,
+
+    /// The number of decimal integers in the password.
+    pub decimal: usize,
+
+    /// The number of special characters in the password.
+    pub specials: usize,
+
+    /// The number of characters that do not belong to any of the character sets.
+    pub other: usize,
+
+    /// The characters used more than once (in the order they are first repeated).
+    pub repeated: Vec<char>,
+
+    /// Is the first character a letter?
+    pub first_is_letter: bool,
+
+    /// An estimate of the password's entropy (in bits).
+    ///
+    /// This is the number of characters multiplied by the base 2 logarithm of the
+    /// number of characters in the sets that the password uses.
+    /// Characters that do not belong to any set only add themselves to the pool.
+    /// This does not account for dictionary words or other predictable patterns,
+    /// so it is an upper bound on the password's strength.
+    pub entropy: f64,
+}
+
+/// Analyze the contents of an existing `password`.
+///
+/// Use [`CharacterSets::analyze_password()`] to analyze a password against custom character sets.
+///
+/// ```rust
+/// use mk_pass::{PasswordRequirements, analyze_password};
+/// let analysis = analyze_password("Tr0ub4dor&3");
+/// assert_eq!(analysis.length, 11);
+/// assert_eq!(analysis.uppercase, 1);
+/// assert_eq!(analysis.lowercase, 6);
+/// assert_eq!(analysis.decimal, 3);
+/// assert_eq!(analysis.specials, 1);
+/// assert_eq!(analysis.repeated, ['r']);
+/// assert!(analysis.first_is_letter);
+/// assert!(!analysis.satisfies(PasswordRequirements::default()));
+/// ```
+pub fn analyze_password(password: &str) -> PasswordAnalysis {
+    CharacterSets::default().analyze_password(password)
+}
+
+impl CharacterSets {
+    /// Analyze the contents of an existing `password` using these character sets.
+    ///
+    /// See also [`analyze_password()`].
+    pub fn analyze_password(&self, password: &str) -> PasswordAnalysis {
+        let is_letter = |ch: &char| self.uppercase().contains(ch) || self.lowercase().contains(ch);
+        let mut analysis = PasswordAnalysis {
+            length: 0,
+            uppercase: 0,
+            lowercase: 0,
+            decimal: 0,
+            specials: 0,
+            other: 0,
+            repeated: vec![],
+            first_is_letter: password.chars().next().is_some_and(|ch| is_letter(&ch)),
+            entropy: 0.0,
+        };
+        let mut seen = HashSet::new();
+        let mut others = HashSet::new();
+        for ch in password.chars() {
+            analysis.length += 1;
+            if self.uppercase().contains(&ch) {
+                analysis.uppercase += 1;
+            } else if self.lowercase().contains(&ch) {
+                analysis.lowercase += 1;
+            } else if self.decimal().contains(&ch) {
+                analysis.decimal += 1;
+            } else if self.special().contains(&ch) {
+                analysis.specials += 1;
+            } else {
+                analysis.other += 1;
+                others.insert(ch);
+            }
+            if !seen.insert(ch) && !analysis.repeated.contains(&ch) {
+                analysis.repeated.push(ch);
+            }
+        }
+
+        let pool = [
+            (analysis.uppercase, self.uppercase().len()),
+            (analysis.lowercase, self.lowercase().len()),
+            (analysis.decimal, self.decimal().len()),
+            (analysis.specials, self.special().len()),
+            (analysis.other, others.len()),
+        ]
+        .iter()
+        .filter_map(|&(count, size)| (count > 0).then_some(size))
+        .sum::<usize>();
+        if pool > 0 {
+            analysis.entropy = analysis.length as f64 * (pool as f64).log2();
+        }
+        analysis
+    }
+}
+
+impl PasswordAnalysis {
+    /// Does the analyzed password satisfy the given `config`?
+    ///
+    /// The `config` is compared as given, without being
+    /// [validated](PasswordRequirements::validate()). So the password must
+    ///
+    /// - be at least as long as the `config` requires,
+    /// - contain at least the required number of each kind of character
+    ///   (and at least 1 uppercase and 1 lowercase letter),
+    /// - not contain more than the maximum number of each kind of character
+    ///   (if a maximum like [`PasswordRequirements::max_decimal`] is specified),
+    /// - start with a letter (if [`PasswordRequirements::first_is_letter`] is enabled), and
+    /// - not repeat any characters (unless [`PasswordRequirements::allow_repeats`] is enabled).
+    ///
+    /// Characters that do not belong to any character set are allowed.
+    pub fn satisfies(&self, config: PasswordRequirements) -> bool {
+        let within = |count: usize, minimum: u16, maximum: Option<u16>| {
+            count >= minimum as usize && maximum.is_none_or(|max| count <= max as usize)
+        };
+        // at least 1 letter of each kind (unless the maximum is 0)
+        let letters =
+            |minimum: u16, maximum: Option<u16>| minimum.max(u16::from(maximum != Some(0)));
+        self.length >= config.length as usize
+            && within(
+                self.uppercase,
+                letters(config.uppercase, config.max_uppercase),
+                config.max_uppercase,
+            )
+            && within(
+                self.lowercase,
+                letters(config.lowercase, config.max_lowercase),
+                config.max_lowercase,
+            )
+            && within(self.decimal, config.decimal, config.max_decimal)
+            && within(self.specials, config.specials, config.max_specials)
+            && (!config.first_is_letter || self.first_is_letter)
+            && (config.allow_repeats || self.repeated.is_empty())
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use rand::{prelude::*, rngs::ThreadRng};
+
+use crate::{CharacterSets, CountDistribution, PasswordRequirements};
+
+/// The number of duplicate passwords in a row after which no more unique passwords are expected
+/// (unless there are more possible passwords than this).
+pub(crate) const MAX_DUPLICATES: usize = 1000;
+
+/// Generate a `count` of passwords given the constraints specified by `config`.
+///
+/// This function will invoke [`PasswordRequirements::validate()`] once for the entire batch.
+/// Use [`CharacterSets::generate_passwords()`] to generate passwords from custom character sets.
+///
+/// If `unique` is `true`, then no two passwords in the batch are the same.
+/// In this case, the `count` is limited to the number of possible passwords
+/// that satisfy the `config`.
+///
+/// ```rust
+/// use mk_pass::{PasswordRequirements, generate_passwords};
+/// let passwords = generate_passwords(PasswordRequirements::default(), 5, true);
+/// assert_eq!(passwords.len(), 5);
+/// ```
+pub fn generate_passwords(config: PasswordRequirements, count: usize, unique: bool) -> Vec<String> {
+    CharacterSets::default().generate_passwords(config, count, unique)
+}
+
+/// Generate a `count` of passwords given the constraints specified by `config` using the given `rng`.
+///
+/// See also [`generate_passwords()`].
+pub fn generate_passwords_with_rng<R: CryptoRng + Rng>(
+    config: PasswordRequirements,
+    count: usize,
+    unique: bool,
+    rng: &mut R,
+) -> Vec<String> {
+    CharacterSets::default().generate_passwords_with_rng(config, count, unique, rng)
+}
+
+/// An iterator of passwords generated from [`CharacterSets`].
+///
+/// This is created by [`CharacterSets::passwords()`] or [`CharacterSets::passwords_with_rng()`].
+/// Each password is generated only when it is requested, so any number of passwords
+/// can be taken without allocating memory for all of them.
+pub struct Passwords<'a, R> {
+    sets: &'a CharacterSets,
+    config: PasswordRequirements,
+    distribution: CountDistribution<'a>,
+    /// The passwords generated so far, if they must be unique.
+    seen: Option<HashSet<String>>,
+    /// The number of possible passwords (or [`usize::MAX`] if there are more).
+    pub(crate) possible: usize,
+    rng: R,
+}
+
+impl<R: CryptoRng + Rng> Iterator for Passwords<'_, R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let Some(seen) = &mut self.seen else {
+            return Some(self.sets.generate_counted_password(
+                self.config,
+                &self.distribution,
+                &mut self.rng,
+            ));
+        };
+        // the possible passwords may be overestimated, so give up after
+        // many more duplicates in a row than there are possible passwords
+        let max_duplicates = self.possible.saturating_mul(16).max(MAX_DUPLICATES);
+        if seen.len() < self.possible {
+            for _ in 0..max_duplicates {
+                let password = self.sets.generate_counted_password(
+                    self.config,
+                    &self.distribution,
+                    &mut self.rng,
+                );
+                if seen.insert(password.clone()) {
+                    return Some(password);
+                }
+            }
+        }
+        // no more unique passwords are expected
+        self.possible = seen.len();
+        None
+    }
+}
+
+impl CharacterSets {
+    /// Generate a `count` of passwords from these character sets given the constraints
+    /// specified by `config`.
+    ///
+    /// This function will invoke [`PasswordRequirements::validate_with()`] once for the entire batch.
+    /// The distribution of the count of each kind of character is also computed only once.
+    /// See also [`generate_passwords()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if both the [`CharacterSets::uppercase()`] and [`CharacterSets::lowercase()`]
+    /// sets are empty.
+    pub fn generate_passwords(
+        &self,
+        config: PasswordRequirements,
+        count: usize,
+        unique: bool,
+    ) -> Vec<String> {
+        self.generate_passwords_with_rng(config, count, unique, &mut rand::rng())
+    }
+
+    /// Generate a `count` of passwords from these character sets given the constraints
+    /// specified by `config` using the given `rng`.
+    ///
+    /// See also [`generate_passwords()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if both the [`CharacterSets::uppercase()`] and [`CharacterSets::lowercase()`]
+    /// sets are empty.
+    pub fn generate_passwords_with_rng<R: CryptoRng + Rng>(
+        &self,
+        config: PasswordRequirements,
+        count: usize,
+        unique: bool,
+        rng: &mut R,
+    ) -> Vec<String> {
+        self.passwords_with_rng(config, unique, rng)
+            .take(count)
+            .collect()
+    }
+
+    /// Generate passwords from these character sets given the constraints specified by `config`,
+    /// one at a time.
+    ///
+    /// This behaves like [`CharacterSets::generate_passwords()`], but the passwords are
+    /// generated as they are taken from the returned iterator.
+    /// If `unique` is `false`, then the iterator never ends.
+    ///
+    /// ```rust
+    /// use mk_pass::{CharacterSets, PasswordRequirements};
+    /// let sets = CharacterSets::default();
+    /// for password in sets.passwords(PasswordRequirements::default(), true).take(3) {
+    ///     assert_eq!(password.len(), 16);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if both the [`CharacterSets::uppercase()`] and [`CharacterSets::lowercase()`]
+    /// sets are empty.
+    pub fn passwords(
+        &self,
+        config: PasswordRequirements,
+        unique: bool,
+    ) -> Passwords<'_, ThreadRng> {
+        self.passwords_with_rng(config, unique, rand::rng())
+    }
+
+    /// Generate passwords from these character sets given the constraints specified by `config`
+    /// using the given `rng`, one at a time.
+    ///
+    /// If `unique` is `true`, then no two passwords are the same, and the iterator ends
+    /// when no more unique passwords are expected (after all possible passwords were generated).
+    /// See also [`CharacterSets::passwords()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if both the [`CharacterSets::uppercase()`] and [`CharacterSets::lowercase()`]
+    /// sets are empty.
+    pub fn passwords_with_rng<R: CryptoRng + Rng>(
+        &self,
+        config: PasswordRequirements,
+        unique: bool,
+        rng: R,
+    ) -> Passwords<'_, R> {
+        let config = config.validate_with(self);
+        let distribution = CountDistribution::new(self, config);
+
+        // do not ask for more unique passwords than there are possible passwords
+        let bits = distribution.entropy();
+        let possible = if bits < usize::BITS as f64 {
+            bits.exp2().round() as usize
+        } else {
+            usize::MAX
+        };
+        Passwords {
+            sets: self,
+            config,
+            distribution,
+            seen: unique.then(HashSet::new),
+            possible,
+            rng,
+        }
+    }
+}
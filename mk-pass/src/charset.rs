@@ -0,0 +1,222 @@
+use std::{borrow::Cow, collections::HashSet};
+
+use unicode_normalization::{
+    IsNormalized, UnicodeNormalization, char::is_combining_mark, is_nfc_quick,
+};
+
+use crate::{
+    Alphabet, RequirementsError,
+    helpers::{AMBIGUOUS_CHARACTERS, CharKind, DECIMAL, LOWERCASE, SPECIAL_CHARACTERS, UPPERCASE},
+};
+
+/// The sets of characters sampled when generating a password.
+///
+/// By default, these are [`UPPERCASE`], [`LOWERCASE`], [`DECIMAL`],
+/// and [`SPECIAL_CHARACTERS`]. Any set can be replaced with user-supplied characters.
+/// This is useful when a target system rejects some of the default characters.
+///
+/// ```rust
+/// use mk_pass::{CharacterSets, PasswordRequirements};
+///
+/// let sets = CharacterSets::default().with_special("-.:+&,@$!_#%~".chars());
+/// let password = sets.generate_password(PasswordRequirements::default());
+/// assert!(!password.contains(['\\', '\'', '/']));
+/// ```
+///
+/// Characters can also be excluded from all sets.
+/// For example, passwords that are read by a person should avoid
+/// characters that look alike:
+///
+/// ```rust
+/// use mk_pass::{AMBIGUOUS_CHARACTERS, CharacterSets, PasswordRequirements};
+///
+/// let sets = CharacterSets::default().without_ambiguous();
+/// let password = sets.generate_password(PasswordRequirements::default());
+/// assert!(!password.contains(AMBIGUOUS_CHARACTERS));
+/// ```
+///
+/// Any Unicode characters can be used, including extended [`Alphabet`]s and ranges of characters:
+///
+/// ```rust
+/// use mk_pass::{Alphabet, CharacterSets, PasswordRequirements};
+///
+/// let sets = CharacterSets::default()
+///     .with_alphabet(Alphabet::Greek)
+///     .with_special('←'..='↓');
+/// let password = sets.generate_password(PasswordRequirements::default());
+/// // the length is the number of characters (not bytes)
+/// assert_eq!(password.chars().count(), 16);
+/// ```
+///
+/// Each character is normalized to Unicode Normalization Form C (NFC),
+/// so generated passwords are always NFC-normalized.
+/// Characters that cannot stand alone in NFC (like combining marks) are ignored.
+/// Duplicate characters in a given set are ignored.
+/// Note, [`PasswordRequirements::validate_with()`](crate::PasswordRequirements::validate_with)
+/// derives its limits from the sizes of these sets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterSets {
+    uppercase: Cow<'static, [char]>,
+    lowercase: Cow<'static, [char]>,
+    decimal: Cow<'static, [char]>,
+    special: Cow<'static, [char]>,
+}
+
+impl Default for CharacterSets {
+    /// Create character sets using the built-in samples.
+    fn default() -> Self {
+        Self {
+            uppercase: Cow::Borrowed(&UPPERCASE),
+            lowercase: Cow::Borrowed(&LOWERCASE),
+            decimal: Cow::Borrowed(&DECIMAL),
+            special: Cow::Borrowed(&SPECIAL_CHARACTERS),
+        }
+    }
+}
+
+/// Normalize the given character to NFC, if it can stand alone in any NFC string.
+///
+/// Combining marks and characters that may compose with a preceding character
+/// would change (or be changed by) their neighbors when a password is normalized.
+fn normalize(ch: char) -> Option<char> {
+    let mut normalized = std::iter::once(ch).nfc();
+    match (normalized.next(), normalized.next()) {
+        (Some(ch), None)
+            if !is_combining_mark(ch) && is_nfc_quick(std::iter::once(ch)) == IsNormalized::Yes =>
+        {
+            Some(ch)
+        }
+        _ => None,
+    }
+}
+
+/// Collect the given characters (normalized to NFC), ignoring any duplicates.
+fn dedup(chars: impl IntoIterator<Item = char>) -> Cow<'static, [char]> {
+    let mut seen = HashSet::new();
+    let set = chars
+        .into_iter()
+        .filter_map(normalize)
+        .filter(|ch| seen.insert(*ch))
+        .collect();
+    Cow::Owned(set)
+}
+
+/// The number of characters in the given `set`.
+///
+/// A password never has more than [`u16::MAX`] characters of any kind,
+/// so a larger set is counted as [`u16::MAX`] characters.
+/// [`CharacterSets::check_sizes()`] reports such sets as an error instead.
+pub(crate) fn set_len(set: &[char]) -> u16 {
+    u16::try_from(set.len()).unwrap_or(u16::MAX)
+}
+
+impl CharacterSets {
+    /// Replace the set of uppercase letters.
+    pub fn with_uppercase(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.uppercase = dedup(chars);
+        self
+    }
+
+    /// Replace the set of lowercase letters.
+    pub fn with_lowercase(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.lowercase = dedup(chars);
+        self
+    }
+
+    /// Replace the set of decimal integers.
+    pub fn with_decimal(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.decimal = dedup(chars);
+        self
+    }
+
+    /// Replace the set of special characters.
+    pub fn with_special(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.special = dedup(chars);
+        self
+    }
+
+    /// Add the letters of the given `alphabet` to the sets of uppercase and lowercase letters.
+    pub fn with_alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.uppercase = dedup(self.uppercase.iter().copied().chain(alphabet.uppercase()));
+        self.lowercase = dedup(self.lowercase.iter().copied().chain(alphabet.lowercase()));
+        self
+    }
+
+    /// Remove the given characters from all sets.
+    ///
+    /// Like the characters of each set, each excluded character is normalized to NFC
+    /// (for example, the Ohm sign U+2126 excludes the Greek letter `Ω` U+03A9).
+    pub fn exclude(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        let excluded = chars
+            .into_iter()
+            .filter_map(normalize)
+            .collect::<HashSet<char>>();
+        for set in [
+            &mut self.uppercase,
+            &mut self.lowercase,
+            &mut self.decimal,
+            &mut self.special,
+        ] {
+            if set.iter().any(|ch| excluded.contains(ch)) {
+                *set = set
+                    .iter()
+                    .filter(|ch| !excluded.contains(ch))
+                    .copied()
+                    .collect();
+            }
+        }
+        self
+    }
+
+    /// Remove the [`AMBIGUOUS_CHARACTERS`] from all sets.
+    ///
+    /// This is a shortcut for `self.exclude(AMBIGUOUS_CHARACTERS)`.
+    pub fn without_ambiguous(self) -> Self {
+        self.exclude(AMBIGUOUS_CHARACTERS)
+    }
+
+    /// The set of uppercase letters.
+    pub fn uppercase(&self) -> &[char] {
+        &self.uppercase
+    }
+
+    /// The set of lowercase letters.
+    pub fn lowercase(&self) -> &[char] {
+        &self.lowercase
+    }
+
+    /// The set of decimal integers.
+    pub fn decimal(&self) -> &[char] {
+        &self.decimal
+    }
+
+    /// The set of special characters.
+    pub fn special(&self) -> &[char] {
+        &self.special
+    }
+
+    /// Checks that no set has more than [`u16::MAX`] characters.
+    pub(crate) fn check_sizes(&self) -> Result<(), RequirementsError> {
+        for (name, set) in [
+            ("uppercase", self.uppercase()),
+            ("lowercase", self.lowercase()),
+            ("decimal", self.decimal()),
+            ("special", self.special()),
+        ] {
+            u16::try_from(set.len()).map_err(|_| RequirementsError::SetTooLarge {
+                name,
+                size: set.len(),
+            })?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn get(&self, kind: CharKind) -> &[char] {
+        match kind {
+            CharKind::Uppercase => self.uppercase(),
+            CharKind::Lowercase => self.lowercase(),
+            CharKind::Decimal => self.decimal(),
+            CharKind::Special => self.special(),
+        }
+    }
+}
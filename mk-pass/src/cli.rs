@@ -0,0 +1,468 @@
+use std::{collections::HashSet, ffi::OsString, io::Write, path::PathBuf};
+
+use clap::{ArgAction, Args, CommandFactory, FromArgMatches, Parser, Subcommand, error::ErrorKind};
+
+#[cfg(feature = "policy")]
+use crate::{Policy, Preset};
+#[cfg(feature = "policy")]
+use clap::ArgMatches;
+
+use crate::{
+    Alphabet, CharacterSets, PassphraseRequirements, PasswordRequirements, PinRequirements,
+    Wordlist, analyze_password, batch::MAX_DUPLICATES, generate_passphrase, generate_pin,
+};
+
+/// A structure to describe the command line interface.
+///
+/// This wraps the [`PasswordRequirements`] with options that do not
+/// describe the contents of a password, such as excluded characters.
+#[derive(Debug, Clone, PartialEq, Eq, Parser)]
+#[command(
+    about = "Generate a password comprehensively.",
+    version,
+    long_about = None,
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub requirements: PasswordRequirements,
+
+    #[cfg(feature = "policy")]
+    #[command(flatten)]
+    pub policy: PolicyArgs,
+
+    /// Extended alphabets of letters to use in the password.
+    #[arg(
+        long,
+        short = 'A',
+        action = ArgAction::Append,
+        long_help = "Extended alphabets of letters to use in the password.\
+        \n\nThe letters of each alphabet are added to the ASCII letters.\n\
+        This option can be specified multiple times to add multiple alphabets.\n\
+        The password's length is counted in characters, not bytes."
+    )]
+    pub alphabet: Vec<Alphabet>,
+
+    /// Characters that should not be used in the password.
+    #[arg(
+        long,
+        short = 'x',
+        default_value = "",
+        hide_default_value = true,
+        long_help = "Characters that should not be used in the password.\
+        \n\nFor example, `--exclude '\\/'` prevents both slash characters from being used.\n\
+        Excluding characters also lowers the maximum length (if repetitions are not allowed)."
+    )]
+    pub exclude: String,
+
+    /// Do not use characters that look alike.
+    #[arg(
+        long,
+        short = 'a',
+        long_help = "Do not use characters that look alike.\
+        \n\nThis excludes the characters `0 O o 1 I l |` which are easily\n\
+        mistaken for one another when read by a person.",
+        action = ArgAction::SetTrue
+    )]
+    pub no_ambiguous: bool,
+
+    /// Show the theoretical entropy of the generated passwords.
+    #[arg(
+        long,
+        short = 'e',
+        long_help = "Show the theoretical entropy of the generated passwords.\
+        \n\nThe entropy (in bits) is printed once on a separate line after the passwords.\n\
+        Every password generated with the same options has the same entropy.",
+        action = ArgAction::SetTrue
+    )]
+    pub show_entropy: bool,
+
+    /// How many passwords should be generated?
+    #[arg(
+        long,
+        short = 'n',
+        default_value = "1",
+        long_help = "How many passwords should be generated?\
+        \n\nEach password is printed on a separate line."
+    )]
+    pub count: usize,
+
+    /// Do not generate the same password more than once.
+    #[arg(
+        long,
+        short = 'u',
+        long_help = "Do not generate the same password more than once.\
+        \n\nThis only applies when generating multiple passwords (see `--count`).\n\
+        The number of passwords is limited to the number of possible passwords.",
+        action = ArgAction::SetTrue
+    )]
+    pub unique: bool,
+
+    /// Generate pronounceable passwords.
+    #[arg(
+        long,
+        short = 'o',
+        conflicts_with = "alphabet",
+        long_help = "Generate pronounceable passwords.\
+        \n\nThe letters alternate between consonant clusters (like `th`) and vowel clusters\n\
+        (like `ou`), which makes the password easier to read aloud.\n\
+        The decimal integers and special characters are still inserted at random positions.\n\
+        Characters may be repeated (as if `--allow-repeats` was given).\n\
+        The lowercase letters must include a consonant and a vowel (see `--exclude`).\n\
+        Pronounceable passwords have much less entropy than other passwords of the same length\n\
+        (see `--show-entropy`).",
+        action = ArgAction::SetTrue
+    )]
+    pub pronounceable: bool,
+}
+
+/// The subcommands of the command line interface.
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Generate a passphrase from a list of words.
+    Passphrase(PassphraseArgs),
+
+    /// Check if an existing password satisfies the password requirements.
+    #[command(
+        long_about = "Check if an existing password satisfies the password requirements.\
+        \n\nThis prints a report about the password's contents.\n\
+        The process exits with a non-zero code if the password does not satisfy the requirements."
+    )]
+    Check(CheckArgs),
+
+    /// Generate a numeric PIN.
+    #[command(long_about = "Generate a numeric PIN.\
+        \n\nPINs with a weak pattern (like `1234`, `1111`, a date, or a commonly used PIN)\n\
+        are never generated.")]
+    Pin(PinArgs),
+}
+
+/// The options of the `passphrase` subcommand.
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct PassphraseArgs {
+    #[command(flatten)]
+    pub requirements: PassphraseRequirements,
+
+    /// A file containing the list of words to use.
+    #[arg(
+        long,
+        short = 'W',
+        long_help = "A file containing the list of words to use.\
+        \n\nEach line of the file should contain a word, optionally preceded by its\n\
+        dice rolls (as in a diceware wordlist).\n\
+        By default, the EFF's large wordlist (7776 words) is used."
+    )]
+    pub wordlist: Option<PathBuf>,
+}
+
+/// The options of the `pin` subcommand.
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct PinArgs {
+    #[command(flatten)]
+    pub requirements: PinRequirements,
+
+    /// How many PINs should be generated?
+    #[arg(
+        long,
+        short = 'n',
+        default_value = "1",
+        long_help = "How many PINs should be generated?\
+        \n\nEach PIN is printed on a separate line."
+    )]
+    pub count: usize,
+}
+
+/// The options of the `check` subcommand.
+#[derive(Debug, Clone, PartialEq, Eq, Args)]
+pub struct CheckArgs {
+    /// The password to check.
+    #[arg(long_help = "The password to check.\
+        \n\nIf not given, the password is read from the first line of stdin.\n\
+        Reading from stdin avoids leaving the password in the shell's history.")]
+    pub password: Option<String>,
+
+    #[command(flatten)]
+    pub requirements: PasswordRequirements,
+
+    #[cfg(feature = "policy")]
+    #[command(flatten)]
+    pub policy: PolicyArgs,
+}
+
+/// The options to load password requirements from a preset or a policy file.
+#[cfg(feature = "policy")]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Args)]
+pub struct PolicyArgs {
+    /// A preset of password requirements derived from a published standard.
+    #[arg(
+        long,
+        short = 't',
+        conflicts_with = "policy",
+        long_help = "A preset of password requirements derived from a published standard.\
+        \n\nAny requirement given on the command line overrides the value from the preset."
+    )]
+    pub preset: Option<Preset>,
+
+    /// A policy file from which the password requirements are loaded.
+    #[arg(
+        long,
+        short = 'p',
+        long_help = "A policy file from which the password requirements are loaded.\
+        \n\nThe file (in TOML, JSON, or YAML format) holds named profiles of password\n\
+        requirements. Any requirement given on the command line overrides the\n\
+        value from the selected profile."
+    )]
+    pub policy: Option<PathBuf>,
+
+    /// The name of the profile to select from the policy file.
+    #[arg(
+        long,
+        short = 'P',
+        requires = "policy",
+        long_help = "The name of the profile to select from the policy file.\
+        \n\nBy default, the profile named `default` is selected\n\
+        (or the only profile if the policy file has exactly 1 profile)."
+    )]
+    pub profile: Option<String>,
+}
+
+#[cfg(feature = "policy")]
+impl PolicyArgs {
+    /// Load the preset or the selected profile of the policy file (if any)
+    /// into the given `requirements`.
+    ///
+    /// Only the `requirements` that were given on the command line (according to
+    /// the parsed `matches`) are kept. If the policy file cannot be loaded, then
+    /// an error is printed and the process exits.
+    pub fn apply(&self, requirements: &mut PasswordRequirements, matches: &ArgMatches) {
+        let mut merged = match (self.preset, &self.policy) {
+            (Some(preset), _) => PasswordRequirements::preset(preset),
+            (None, Some(path)) => Policy::from_file(path)
+                .and_then(|policy| policy.profile(self.profile.as_deref()))
+                .unwrap_or_else(|e| Cli::command().error(ErrorKind::ValueValidation, e).exit()),
+            (None, None) => return,
+        };
+        let given =
+            |id: &str| matches.value_source(id) == Some(clap::parser::ValueSource::CommandLine);
+        macro_rules! keep_given {
+            ($($field:ident),*) => {
+                $(
+                    if given(stringify!($field)) {
+                        merged.$field = requirements.$field;
+                    }
+                )*
+            };
+        }
+        keep_given!(
+            length,
+            decimal,
+            max_decimal,
+            specials,
+            max_specials,
+            uppercase,
+            max_uppercase,
+            lowercase,
+            max_lowercase,
+            first_is_letter,
+            allow_repeats
+        );
+        *requirements = merged;
+    }
+}
+
+impl CheckArgs {
+    /// Analyze the password and print a report to stdout.
+    ///
+    /// Returns `true` if the password satisfies the requirements.
+    pub fn run(&self) -> bool {
+        let password = match &self.password {
+            Some(password) => password.clone(),
+            None => {
+                let mut line = String::new();
+                std::io::stdin()
+                    .read_line(&mut line)
+                    .unwrap_or_else(|e| Cli::command().error(ErrorKind::Io, e).exit());
+                line.trim_end_matches(['\r', '\n']).to_string()
+            }
+        };
+        let analysis = analyze_password(&password);
+        let yes_no = |value: bool| if value { "yes" } else { "no" };
+        let repeated = analysis
+            .repeated
+            .iter()
+            .map(char::to_string)
+            .collect::<Vec<String>>();
+        let satisfied = analysis.satisfies(self.requirements);
+        println!("Length: {}", analysis.length);
+        println!("Uppercase: {}", analysis.uppercase);
+        println!("Lowercase: {}", analysis.lowercase);
+        println!("Decimal: {}", analysis.decimal);
+        println!("Specials: {}", analysis.specials);
+        println!("Other: {}", analysis.other);
+        if repeated.is_empty() {
+            println!("Repeated: none");
+        } else {
+            println!("Repeated: {}", repeated.join(" "));
+        }
+        println!("First is letter: {}", yes_no(analysis.first_is_letter));
+        println!("Entropy: {:.2} bits", analysis.entropy);
+        println!("Satisfies requirements: {}", yes_no(satisfied));
+        satisfied
+    }
+}
+
+impl Cli {
+    /// Parse the command line `args` (including the program's name).
+    ///
+    /// Unlike [`Parser::parse_from()`], this also loads the password requirements
+    /// from a preset or a policy file (if one is given). If the `args` are invalid, then an
+    /// error is printed and the process exits.
+    pub fn parse_args<I, T>(args: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let matches = Self::command().get_matches_from(args);
+        #[allow(unused_mut)]
+        let mut cli = Self::from_arg_matches(&matches)
+            .unwrap_or_else(|e| e.format(&mut Self::command()).exit());
+        #[cfg(feature = "policy")]
+        cli.apply_policy(&matches);
+        cli
+    }
+
+    /// Load the password requirements from a preset or a policy file (if one is given).
+    #[cfg(feature = "policy")]
+    fn apply_policy(&mut self, matches: &ArgMatches) {
+        match (&mut self.command, matches.subcommand()) {
+            (None, _) => self.policy.apply(&mut self.requirements, matches),
+            (Some(Command::Check(args)), Some((_, matches))) => {
+                args.policy.apply(&mut args.requirements, matches)
+            }
+            _ => {}
+        }
+    }
+
+    /// The character sets described by the CLI options.
+    pub fn character_sets(&self) -> CharacterSets {
+        let sets = self
+            .alphabet
+            .iter()
+            .fold(CharacterSets::default(), |sets, alphabet| {
+                sets.with_alphabet(*alphabet)
+            })
+            .exclude(self.exclude.chars());
+        if self.no_ambiguous {
+            sets.without_ambiguous()
+        } else {
+            sets
+        }
+    }
+
+    /// Generate the pronounceable passwords specified by the CLI options, one at a time.
+    ///
+    /// If `--unique` passwords are requested, then this ends early when too many generated
+    /// passwords in a row are duplicates (because there are not enough possible passwords).
+    pub(crate) fn pronounceable_passwords<'a>(
+        &'a self,
+        sets: &'a CharacterSets,
+    ) -> impl Iterator<Item = String> + 'a {
+        let mut seen = HashSet::new();
+        std::iter::from_fn(move || {
+            for _ in 0..MAX_DUPLICATES {
+                let password = sets.generate_pronounceable_password(self.requirements);
+                if !self.unique || seen.insert(password.clone()) {
+                    return Some(password);
+                }
+            }
+            None
+        })
+        .fuse()
+        .take(self.count)
+    }
+
+    /// Generate passwords (or a passphrase) as specified by the CLI options and print them to stdout.
+    ///
+    /// If the CLI options exclude all letters or the given wordlist cannot be read,
+    /// then an error is printed and the process exits.
+    /// If a checked password does not satisfy the requirements, then the process exits
+    /// with a non-zero code.
+    pub fn run(&self) {
+        match &self.command {
+            Some(Command::Passphrase(args)) => {
+                let wordlist = match &args.wordlist {
+                    Some(path) => Wordlist::from_file(path)
+                        .unwrap_or_else(|e| Self::command().error(ErrorKind::Io, e).exit()),
+                    None => Wordlist::default(),
+                };
+                println!("{}", generate_passphrase(&args.requirements, &wordlist));
+                return;
+            }
+            Some(Command::Check(args)) => {
+                if !args.run() {
+                    std::process::exit(1);
+                }
+                return;
+            }
+            Some(Command::Pin(args)) => {
+                for _ in 0..args.count {
+                    println!("{}", generate_pin(args.requirements));
+                }
+                return;
+            }
+            None => {}
+        }
+        let sets = self.character_sets();
+        if sets.uppercase().is_empty() && sets.lowercase().is_empty() {
+            Self::command()
+                .error(
+                    ErrorKind::ValueValidation,
+                    "The excluded characters cannot include all letters",
+                )
+                .exit();
+        }
+        // print each password as it is generated, so any count can be printed
+        // (until stdout is closed, like when piped to `head`)
+        let mut stdout = std::io::stdout().lock();
+        let bits = if self.pronounceable {
+            if let Err(e) = sets.check_pronounceable() {
+                Self::command().error(ErrorKind::ValueValidation, e).exit();
+            }
+            let mut printed = 0;
+            for password in self.pronounceable_passwords(&sets) {
+                if writeln!(stdout, "{password}").is_err() {
+                    return;
+                }
+                printed += 1;
+            }
+            if printed < self.count {
+                Self::command()
+                    .error(
+                        ErrorKind::ValueValidation,
+                        format!(
+                            "There are not enough possible pronounceable passwords to generate {} unique passwords",
+                            self.count
+                        ),
+                    )
+                    .exit();
+            }
+            sets.pronounceable_entropy(self.requirements)
+        } else {
+            for password in sets
+                .passwords(self.requirements, self.unique)
+                .take(self.count)
+            {
+                if writeln!(stdout, "{password}").is_err() {
+                    return;
+                }
+            }
+            sets.password_entropy(self.requirements)
+        };
+        if self.show_entropy {
+            println!("Entropy: {bits:.2} bits");
+        }
+    }
+}
@@ -1,28 +1,164 @@
-use crate::helpers::{DECIMAL, LOWERCASE, SPECIAL_CHARACTERS, UPPERCASE};
+use crate::{CharacterSets, RequirementsError, charset::set_len};
+
+/// The minimum length of a password.
+pub(crate) const MIN_LENGTH: u16 = 10;
 
 #[cfg(feature = "clap")]
 use clap::{ArgAction, Parser};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// A structure to describe password requirements.
+///
+/// With the `serde` feature enabled, this can be (de)serialized using the
+/// camelCase field names of the Node.js and C++ bindings (`firstIsLetter`, `maxDecimal`, etc.).
+/// Missing fields use their default values, and unspecified maximums are omitted.
 #[cfg_attr(
     feature = "clap",
     derive(Parser),
     command(about = "Generate a password comprehensively.", version, long_about = None)
 )]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(default, deny_unknown_fields, rename_all = "camelCase")
+)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PasswordRequirements {
     /// The length of the password.
+    ///
+    /// This is the number of characters (Unicode code points), not the number of bytes.
+    /// Letters of extended alphabets take more than 1 byte when encoded as UTF-8.
     #[cfg_attr(feature = "clap", arg(long, short, default_value = "16"))]
     pub length: u16,
 
     /// How many decimal integer characters should the password contain?
-    #[cfg_attr(feature = "clap", arg(long, short, default_value = "1"))]
+    ///
+    /// If [`PasswordRequirements::max_decimal`] is specified, then this is the minimum count.
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long,
+            short,
+            default_value = "1",
+            long_help = "How many decimal integer characters should the password contain?\
+            \n\nIf `--max-decimal` is specified, then this is the minimum count."
+        )
+    )]
     pub decimal: u16,
 
+    /// At most how many decimal integer characters should the password contain?
+    ///
+    /// If specified, the actual count is picked randomly from
+    /// [`PasswordRequirements::decimal`] to this value (inclusive).
+    /// Otherwise, the password contains exactly [`PasswordRequirements::decimal`] integers.
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long,
+            short = 'D',
+            long_help = "At most how many decimal integer characters should the password contain?\
+            \n\nIf specified, the actual count is picked randomly from `--decimal` to this value.\n\
+            By default, the password contains exactly `--decimal` integers."
+        )
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_decimal: Option<u16>,
+
     /// How many special characters should the password contain?
-    #[cfg_attr(feature = "clap", arg(long, short, default_value = "1"))]
+    ///
+    /// If [`PasswordRequirements::max_specials`] is specified, then this is the minimum count.
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long,
+            short,
+            default_value = "1",
+            long_help = "How many special characters should the password contain?\
+            \n\nIf `--max-specials` is specified, then this is the minimum count."
+        )
+    )]
     pub specials: u16,
 
+    /// At most how many special characters should the password contain?
+    ///
+    /// If specified, the actual count is picked randomly from
+    /// [`PasswordRequirements::specials`] to this value (inclusive).
+    /// Otherwise, the password contains exactly [`PasswordRequirements::specials`] characters.
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long,
+            short = 'S',
+            long_help = "At most how many special characters should the password contain?\
+            \n\nIf specified, the actual count is picked randomly from `--specials` to this value.\n\
+            By default, the password contains exactly `--specials` characters."
+        )
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_specials: Option<u16>,
+
+    /// At least how many uppercase letters should the password contain?
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long,
+            short = 'U',
+            default_value = "0",
+            long_help = "At least how many uppercase letters should the password contain?\
+            \n\nBy default, letters are split evenly between uppercase and lowercase."
+        )
+    )]
+    pub uppercase: u16,
+
+    /// At most how many uppercase letters should the password contain?
+    ///
+    /// If specified (for either kind of letter), the actual number of uppercase letters
+    /// is picked randomly from [`PasswordRequirements::uppercase`] to this value (inclusive).
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long,
+            short = 'M',
+            long_help = "At most how many uppercase letters should the password contain?\
+            \n\nIf specified (for either kind of letter), the actual number of uppercase letters\n\
+            is picked randomly from `--uppercase` to this value."
+        )
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_uppercase: Option<u16>,
+
+    /// At least how many lowercase letters should the password contain?
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long,
+            short = 'L',
+            default_value = "0",
+            long_help = "At least how many lowercase letters should the password contain?\
+            \n\nBy default, letters are split evenly between uppercase and lowercase."
+        )
+    )]
+    pub lowercase: u16,
+
+    /// At most how many lowercase letters should the password contain?
+    ///
+    /// If specified (for either kind of letter), the actual number of lowercase letters
+    /// is picked randomly from [`PasswordRequirements::lowercase`] to this value (inclusive).
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            long,
+            short = 'm',
+            long_help = "At most how many lowercase letters should the password contain?\
+            \n\nIf specified (for either kind of letter), the actual number of lowercase letters\n\
+            is picked randomly from `--lowercase` to this value."
+        )
+    )]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max_lowercase: Option<u16>,
+
     /// Should the first character always be a letter?
     #[cfg_attr(
         feature = "clap",
@@ -62,26 +198,48 @@ impl PasswordRequirements {
     /// The phrase "sane minimum requirements" implies
     ///
     /// 1. `length` is not less than 10
-    /// 2. To avoid repetitions, `length` is not more than
+    /// 2. To avoid repetitions, `length` is not more than the 52 letters plus
+    ///    the (maximum) counts of decimal integers and special characters. For example,
     ///
-    ///    - 52 if only letters (no decimal integers or special characters) are used
-    ///    - 62 if only letters and decimal integers are used
-    ///    - 68 if only letters and special characters are used
-    ///    - 78 if letters, decimal integers, and special characters are used
+    ///    - 54 if the password contains 1 decimal integer and 1 special character (the default)
+    ///    - 78 if the password contains 10 decimal integers and 16 special characters
     ///    - [u16::MAX] if repeated characters are allowed
-    /// 3. `specials` character count does not overrule the required number of
+    /// 3. `uppercase` and `lowercase` letter counts do not overrule the required number of
+    ///
+    ///    - lowercase letters (1; if `uppercase` is the overruling count)
+    ///    - decimal integers (1; if `decimal` is specified as non-zero value)
+    ///    - special characters (1; if `specials` is specified as non-zero value)
+    /// 4. `specials` character count does not overrule the required number of
     ///
-    ///    - letters (2; 1 uppercase and 1 lowercase)
+    ///    - letters (at least 2; 1 uppercase and 1 lowercase or
+    ///      the specified `uppercase` and `lowercase` counts,
+    ///      excluding any kind of letter whose maximum count is 0)
     ///    - decimal integers (if `decimal` is specified as non-zero value)
-    /// 4. `decimal` character count does not overrule the required number of
+    /// 5. `decimal` character count does not overrule the required number of
     ///
-    ///    - letters (2; 1 uppercase and 1 lowercase)
+    ///    - letters (as described above)
     ///    - special characters (if `specials` is specified as non-zero value)
+    /// 6. To avoid repetitions, `decimal` is not more than 10, `specials` is not more than 16,
+    ///    and `uppercase` and `lowercase` are each not more than 26.
+    ///    Consequently, `length` is shortened if there are not enough letters (52)
+    ///    to fill the remainder.
+    /// 7. Each maximum count (like `max_decimal`) is not less than the respective minimum count.
+    ///    Consequently, `length` is shortened if the maximum counts cannot fill it.
+    /// 8. The maximum counts of letters allow enough letters for the first character
+    ///    (if `first_is_letter`) and for the length of 10 that other characters cannot fill.
+    ///    Otherwise, `max_lowercase` (then `max_uppercase`) is raised.
+    ///    The `length` is shortened (as in 7) only after these counts are resolved,
+    ///    and the other counts are then reconciled with the shortened `length`.
+    ///
+    /// These limits are derived from the default [`CharacterSets`].
+    /// Use [`PasswordRequirements::validate_with()`] for custom character sets.
+    /// Use [`PasswordRequirements::check()`] to get an error instead of changed values.
     ///
     /// # About resolving conflicts
     ///
     /// If this function finds a conflict between the specified number of
     /// `specials` characters and `decimal`, then decimal integers takes precedence.
+    /// Likewise, `uppercase` letters take precedence over `lowercase` letters.
     ///
     /// For example:
     ///
@@ -96,47 +254,332 @@ impl PasswordRequirements {
     /// let expected = PasswordRequirements {
     ///     length: 16,
     ///     specials: 1,
-    ///     decimal: 13,
+    ///     decimal: 10,
     ///     ..Default::default()
     /// };
     /// assert_eq!(req.validate(), expected);
     /// ```
     pub fn validate(&self) -> Self {
-        let mut len = self.length.max(10);
+        self.validate_with(&CharacterSets::default())
+    }
+
+    /// Validates the instance's values against the given character `sets`.
+    ///
+    /// This behaves like [`PasswordRequirements::validate()`], but the limits that
+    /// avoid repetitions are derived from the sizes of the given `sets`.
+    /// If `sets` are too small, then the resulting `length` may be less than 10.
+    ///
+    /// ```rust
+    /// use mk_pass::{CharacterSets, PasswordRequirements};
+    /// let sets = CharacterSets::default().with_special(['-', '_', '.']);
+    /// let req = PasswordRequirements {
+    ///     length: 100,
+    ///     specials: 5,
+    ///     ..Default::default()
+    /// };
+    /// let expected = PasswordRequirements {
+    ///     length: 56,
+    ///     specials: 3,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(req.validate_with(&sets), expected);
+    /// ```
+    pub fn validate_with(&self, sets: &CharacterSets) -> Self {
+        self.resolve(sets).0
+    }
+
+    /// Checks the instance's values without changing them.
+    ///
+    /// This returns an error describing the first constraint (as described in
+    /// [`PasswordRequirements::validate()`]) that the instance violates.
+    /// In other words, this only returns [`Ok`] if
+    /// [`PasswordRequirements::validate()`] would not change any values.
+    ///
+    /// ```rust
+    /// use mk_pass::{PasswordRequirements, RequirementsError};
+    /// let req = PasswordRequirements {
+    ///     length: 8,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     req.check(),
+    ///     Err(RequirementsError::TooShort { length: 8, minimum: 10 })
+    /// );
+    /// assert!(req.validate().check().is_ok());
+    /// ```
+    pub fn check(&self) -> Result<(), RequirementsError> {
+        self.check_with(&CharacterSets::default())
+    }
+
+    /// Checks the instance's values against the given character `sets` without changing them.
+    ///
+    /// This behaves like [`PasswordRequirements::check()`], but the limits that
+    /// avoid repetitions are derived from the sizes of the given `sets`.
+    /// Any set with more than [`u16::MAX`] characters is reported as
+    /// [`RequirementsError::SetTooLarge`].
+    /// See also [`PasswordRequirements::validate_with()`].
+    pub fn check_with(&self, sets: &CharacterSets) -> Result<(), RequirementsError> {
+        if sets.uppercase().is_empty() && sets.lowercase().is_empty() {
+            return Err(RequirementsError::NoLetters);
+        }
+        sets.check_sizes()?;
+        match self.resolve(sets) {
+            (validated, Some(err)) if validated != *self => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolves the instance's values against the given character `sets`.
+    ///
+    /// This returns the validated values and the first violated constraint (if any).
+    ///
+    /// The length is resolved first (including the maximum counts of letters needed to fill it),
+    /// then the counts of each kind of character are [reconciled](Self::reconcile())
+    /// with the resolved length.
+    fn resolve(&self, sets: &CharacterSets) -> (Self, Option<RequirementsError>) {
+        let mut violation = None;
+        let upper_len = set_len(sets.uppercase());
+        let lower_len = set_len(sets.lowercase());
+        let letters_len = upper_len.saturating_add(lower_len);
+        let decimal_len = set_len(sets.decimal());
+        let special_len = set_len(sets.special());
+
+        let mut len = self.length.max(MIN_LENGTH);
+        if len != self.length {
+            violation.get_or_insert(RequirementsError::TooShort {
+                length: self.length,
+                minimum: MIN_LENGTH,
+            });
+        }
         if !self.allow_repeats {
-            len = len.min(
-                UPPERCASE.len() as u16
-                    + LOWERCASE.len() as u16
-                    + {
-                        if self.specials > 0 {
-                            SPECIAL_CHARACTERS.len() as u16
-                        } else {
-                            0
-                        }
+            let mut max_len = letters_len;
+            if self.specials.max(self.max_specials.unwrap_or_default()) > 0 {
+                max_len = max_len.saturating_add(special_len);
+            }
+            if self.decimal.max(self.max_decimal.unwrap_or_default()) > 0 {
+                max_len = max_len.saturating_add(decimal_len);
+            }
+            if len > max_len {
+                violation.get_or_insert(RequirementsError::TooLong {
+                    length: self.length,
+                    maximum: max_len,
+                });
+                len = max_len;
+            }
+        }
+        let min_len = len.min(MIN_LENGTH);
+
+        // the most characters of each kind that the sets can provide
+        let limit = |count: Option<u16>, set_len: u16| {
+            let count = count.unwrap_or(u16::MAX);
+            if self.allow_repeats && set_len > 0 {
+                count
+            } else {
+                count.min(set_len)
+            }
+        };
+        let repeatable = |set_len: u16| {
+            if self.allow_repeats {
+                u16::MAX
+            } else {
+                set_len
+            }
+        };
+        let limits = [
+            limit(None, upper_len),
+            limit(None, lower_len),
+            repeatable(decimal_len),
+            repeatable(special_len),
+        ];
+
+        // each maximum count is not less than the respective minimum count
+        let mut bounds_violation = None;
+        let mut range_max = |name, minimum: u16, limit: u16, maximum| match maximum {
+            Some(max) if max < minimum => {
+                bounds_violation.get_or_insert(RequirementsError::MaximumLessThanMinimum {
+                    name,
+                    minimum,
+                    maximum: max,
+                });
+                Some(minimum.min(limit))
+            }
+            _ => maximum,
+        };
+        let max_uppercase = range_max("uppercase", self.uppercase, limits[0], self.max_uppercase);
+        let max_lowercase = range_max("lowercase", self.lowercase, limits[1], self.max_lowercase);
+        let max_decimal = range_max("decimal", self.decimal, limits[2], self.max_decimal);
+        let max_specials = range_max("specials", self.specials, limits[3], self.max_specials);
+        let (mut max_uppercase, mut max_lowercase) = (max_uppercase, max_lowercase);
+
+        // the maximum counts must be able to fill the length, and letters are needed for the
+        // first character and for any (minimum) length that the other characters cannot fill
+        let mut letters_violation = None;
+        let mut too_long = None;
+        let (counts, counts_violation) = loop {
+            let letter_limits = [
+                limit(max_uppercase, upper_len),
+                limit(max_lowercase, lower_len),
+            ];
+            let (counts, counts_violation) = self.reconcile(len, limits, letter_limits);
+            let [_, _, decimal, specials] = counts;
+            let letters = letter_limits[0] as u32 + letter_limits[1] as u32;
+            let others = limit(max_decimal.or(Some(decimal)), decimal_len) as u32
+                + limit(max_specials.or(Some(specials)), special_len) as u32;
+            let needed = (min_len as u32)
+                .saturating_sub(others)
+                .max(u32::from(self.first_is_letter));
+            if letters < needed {
+                let mut missing = (needed - letters) as u16;
+                for (maximum, set_len) in [
+                    (&mut max_lowercase, lower_len),
+                    (&mut max_uppercase, upper_len),
+                ] {
+                    if let Some(max) = maximum {
+                        let raised =
+                            (limit(None, set_len) - limit(Some(*max), set_len)).min(missing);
+                        *max += raised;
+                        missing -= raised;
                     }
-                    + {
-                        if self.decimal > 0 {
-                            DECIMAL.len() as u16
-                        } else {
-                            0
-                        }
-                    },
-            );
+                }
+                if missing < (needed - letters) as u16 {
+                    letters_violation.get_or_insert(RequirementsError::TooFewLetters {
+                        letters: letters as u16,
+                        minimum: needed as u16,
+                    });
+                    continue;
+                }
+            }
+            if len as u32 > letters + others {
+                len = (letters + others) as u16;
+                too_long = Some(len);
+                continue;
+            }
+            break (counts, counts_violation);
+        };
+        let [uppercase, lowercase, decimal, specials] = counts;
+
+        if let (Some(RequirementsError::TooLong { maximum, .. }), Some(len)) =
+            (&mut violation, too_long)
+        {
+            // report the final maximum length, not the one found first
+            *maximum = len;
+        }
+        let violation = violation
+            .or(counts_violation)
+            .or(bounds_violation)
+            .or(letters_violation)
+            .or(too_long.map(|maximum| RequirementsError::TooLong {
+                length: self.length,
+                maximum,
+            }));
+
+        let validated = Self {
+            length: len,
+            decimal,
+            max_decimal,
+            specials,
+            max_specials,
+            uppercase,
+            max_uppercase,
+            lowercase,
+            max_lowercase,
+            first_is_letter: self.first_is_letter,
+            allow_repeats: self.allow_repeats,
+        };
+        (validated, violation)
+    }
+
+    /// Reconciles the counts of each kind of character with the resolved `len`gth.
+    ///
+    /// The `limits` are the most characters of each kind (uppercase letters, lowercase letters,
+    /// decimal integers, and special characters) that the sets can provide,
+    /// and the `letter_limits` also account for the maximum counts of letters.
+    /// This returns the counts (in the same order) and the first violated constraint (if any).
+    fn reconcile(
+        &self,
+        len: u16,
+        limits: [u16; 4],
+        letter_limits: [u16; 2],
+    ) -> ([u16; 4], Option<RequirementsError>) {
+        let mut violation = None;
+        let [upper_len, lower_len, decimal_len, special_len] = limits;
+        let [max_upper, max_lower] = letter_limits;
+
+        let mut uppercase = self.uppercase;
+        let mut lowercase = self.lowercase;
+        if uppercase > upper_len {
+            violation.get_or_insert(RequirementsError::TooManyUppercase {
+                uppercase: self.uppercase,
+                maximum: upper_len,
+            });
+            uppercase = upper_len;
+        }
+        if lowercase > lower_len {
+            violation.get_or_insert(RequirementsError::TooManyLowercase {
+                lowercase: self.lowercase,
+                maximum: lower_len,
+            });
+            lowercase = lower_len;
+        }
+        let letter_max_len = len
+            .saturating_sub(u16::from(self.decimal > 0))
+            .saturating_sub(u16::from(self.specials > 0));
+        let upper_limit = letter_max_len.saturating_sub(u16::from(max_lower > 0));
+        if uppercase > upper_limit {
+            violation.get_or_insert(RequirementsError::UppercaseOverrulesOthers {
+                uppercase: self.uppercase,
+                maximum: upper_limit,
+            });
+            uppercase = upper_limit;
+        }
+        // at least 1 letter of each kind is used, unless its maximum count is 0
+        let upper_used = uppercase.max(1).min(max_upper);
+        let lower_limit = letter_max_len.saturating_sub(upper_used);
+        if lowercase > lower_limit {
+            violation.get_or_insert(RequirementsError::LowercaseOverrulesOthers {
+                lowercase: self.lowercase,
+                maximum: lower_limit,
+            });
+            lowercase = lower_limit;
         }
-        let non_letter_max_len = len - 2;
+        let lower_used = lowercase.max(1).min(max_lower);
+
+        let non_letter_max_len = len.saturating_sub(upper_used + lower_used);
         let max_special = if self.specials > 0 {
-            non_letter_max_len - self.decimal.min(non_letter_max_len - 1)
+            non_letter_max_len - self.decimal.min(non_letter_max_len.saturating_sub(1))
         } else {
             0
         };
         let max_decimal = non_letter_max_len - max_special;
-        Self {
-            length: len,
-            decimal: self.decimal.min(max_decimal),
-            specials: self.specials.min(max_special),
-            first_is_letter: self.first_is_letter,
-            allow_repeats: self.allow_repeats,
+        let mut decimal = self.decimal.min(max_decimal);
+        if decimal != self.decimal {
+            violation.get_or_insert(RequirementsError::DecimalOverrulesOthers {
+                decimal: self.decimal,
+                maximum: max_decimal,
+            });
+        }
+        let mut specials = self.specials.min(max_special);
+        if specials != self.specials {
+            violation.get_or_insert(RequirementsError::SpecialsOverruleOthers {
+                specials: self.specials,
+                maximum: max_special,
+            });
+        }
+        if decimal > decimal_len {
+            violation.get_or_insert(RequirementsError::TooManyDecimal {
+                decimal: self.decimal,
+                maximum: decimal_len,
+            });
+            decimal = decimal_len;
+        }
+        if specials > special_len {
+            violation.get_or_insert(RequirementsError::TooManySpecials {
+                specials: self.specials,
+                maximum: special_len,
+            });
+            specials = special_len;
         }
+        ([uppercase, lowercase, decimal, specials], violation)
     }
 }
 
@@ -147,6 +590,12 @@ impl Default for PasswordRequirements {
             length: 16,
             decimal: 1,
             specials: 1,
+            max_decimal: None,
+            max_specials: None,
+            uppercase: 0,
+            lowercase: 0,
+            max_uppercase: None,
+            max_lowercase: None,
             first_is_letter: true,
             allow_repeats: false,
         }
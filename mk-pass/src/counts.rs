@@ -0,0 +1,312 @@
+use rand::prelude::*;
+
+use crate::{
+    CharacterSets, PasswordRequirements, entropy::Log2Factorials, helpers::CountTypesUsed,
+};
+
+/// Counts that are this many bits less likely than the most likely count are negligible.
+const NEGLIGIBLE_BITS: f64 = 64.0;
+
+/// A kind of character whose count is picked by a [`CountDistribution`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Decimal,
+    Special,
+    Uppercase,
+    Lowercase,
+    /// Both kinds of letters, [split](CharacterSets::split_letters()) instead of picked.
+    Letters,
+}
+
+/// The distribution of the count of each kind of character in a password
+/// with a validated `config`.
+///
+/// Each combination of counts is weighted by the number of possible passwords with those counts,
+/// so every possible password is equally likely.
+/// The counts are picked one kind at a time (decimal integers, special characters, then letters)
+/// from the marginal weights of each count given the remaining length.
+///
+/// The weights of each kind are log-concave, so only the counts near the most likely count
+/// are considered. The others are less likely by more than [`NEGLIGIBLE_BITS`].
+/// For long passwords, these counts span a range that grows like the square root of the length,
+/// so the weights are computed in time linear in the length of the password.
+/// They are computed once, then reused for each password.
+pub(crate) struct CountDistribution<'a> {
+    sets: &'a CharacterSets,
+    config: PasswordRequirements,
+    /// The kind of character and the range of counts for each stage.
+    stages: Vec<(Stage, u16, u16)>,
+    /// The `log2` of the weight of each count (up to the length) of each stage.
+    weights: Vec<Vec<f64>>,
+    /// The sums of the minimum and maximum counts of each stage and the stages after it.
+    rest: Vec<(u32, u32)>,
+    /// The `log2` of the total weight of the counts of each stage given the remaining length.
+    ///
+    /// This is `NaN` if not computed yet.
+    totals: Vec<Vec<f64>>,
+    /// The (non-negligible) range of counts of each stage given the remaining length.
+    windows: Vec<Vec<(u16, u16)>>,
+    /// The last most likely count of each stage, where the search for the next one starts.
+    modes: Vec<u16>,
+}
+
+impl<'a> CountDistribution<'a> {
+    /// Compute the distribution of counts for a validated `config` and the given `sets`.
+    pub(crate) fn new(sets: &'a CharacterSets, config: PasswordRequirements) -> Self {
+        let [decimal, special, uppercase, lowercase] = sets.count_ranges(config);
+        let stages = if sets.splits_letters(config) {
+            let letters = (
+                uppercase.0.saturating_add(lowercase.0),
+                uppercase.1.saturating_add(lowercase.1),
+            );
+            vec![
+                (Stage::Decimal, decimal.0, decimal.1),
+                (Stage::Special, special.0, special.1),
+                (Stage::Letters, letters.0, letters.1),
+            ]
+        } else {
+            vec![
+                (Stage::Decimal, decimal.0, decimal.1),
+                (Stage::Special, special.0, special.1),
+                (Stage::Uppercase, uppercase.0, uppercase.1),
+                (Stage::Lowercase, lowercase.0, lowercase.1),
+            ]
+        };
+        let mut rest = vec![(0, 0); stages.len() + 1];
+        for (j, &(_, min, max)) in stages.iter().enumerate().rev() {
+            rest[j] = (rest[j + 1].0 + min as u32, rest[j + 1].1 + max as u32);
+        }
+        let len = config.length as usize + 1;
+        let factorials = sets.log2_factorials(config);
+        let weights = stages
+            .iter()
+            .map(|&(stage, min, max)| {
+                (0..=config.length)
+                    .map(|count| match (min..=max).contains(&count) {
+                        true => sets.log_count(config, stage, count, &factorials),
+                        false => f64::NEG_INFINITY,
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut distribution = Self {
+            sets,
+            config,
+            weights,
+            totals: vec![vec![f64::NAN; len]; stages.len()],
+            windows: vec![vec![(0, 0); len]; stages.len()],
+            modes: vec![0; stages.len()],
+            stages,
+            rest,
+        };
+        distribution.modes = distribution.joint_mode();
+        distribution.log_total(0, config.length);
+        distribution
+    }
+
+    /// The `log2` of the number of possible passwords with the validated `config`.
+    pub(crate) fn entropy(&self) -> f64 {
+        let len = self.config.length as usize;
+        let factorials = Log2Factorials::new(len);
+        let arrangements = if self.config.first_is_letter {
+            factorials.factorial(len.saturating_sub(1))
+        } else {
+            factorials.factorial(len)
+        };
+        arrangements + self.cached_total(0, self.config.length)
+    }
+
+    /// Pick the count of each kind of character.
+    pub(crate) fn pick<R: Rng>(&self, rng: &mut R) -> CountTypesUsed {
+        let mut counts = CountTypesUsed::default();
+        let mut remaining = self.config.length;
+        for (j, &(stage, _, _)) in self.stages.iter().enumerate() {
+            let count = if j + 1 == self.stages.len() {
+                remaining
+            } else {
+                let (min, max) = self.windows[j][remaining as usize];
+                let total = self.totals[j][remaining as usize];
+                let mut target = rng.random::<f64>();
+                let mut picked = max;
+                for count in min..=max {
+                    let weight = self.weights[j][count as usize]
+                        + self.cached_total(j + 1, remaining - count)
+                        - total;
+                    let probability = weight.exp2();
+                    if target < probability {
+                        picked = count;
+                        break;
+                    }
+                    target -= probability;
+                }
+                // only falls through to the maximum due to rounding errors
+                picked
+            };
+            match stage {
+                Stage::Decimal => counts.number = count,
+                Stage::Special => counts.special = count,
+                Stage::Uppercase => counts.uppercase = count,
+                Stage::Lowercase => counts.lowercase = count,
+                Stage::Letters => {
+                    (counts.uppercase, counts.lowercase) =
+                        self.sets.split_letters(self.config, count);
+                }
+            }
+            remaining -= count;
+        }
+        counts
+    }
+
+    /// The `log2` of the number of ways a password can start with `letters`,
+    /// if the [`PasswordRequirements::first_is_letter`] is enabled.
+    fn log_first(&self, j: usize, letters: u16) -> f64 {
+        let starts_letters = matches!(self.stages[j].0, Stage::Uppercase | Stage::Letters);
+        if self.config.first_is_letter && starts_letters {
+            (letters as f64).log2()
+        } else {
+            0.0
+        }
+    }
+
+    /// The range of counts of the stage `j` that leave a `remaining` length
+    /// that the later stages can fill.
+    fn choices(&self, j: usize, remaining: u16) -> Option<(u16, u16)> {
+        let (_, min, max) = self.stages[j];
+        let (rest_min, rest_max) = self.rest[j + 1];
+        let remaining = remaining as u32;
+        let min = (min as u32).max(remaining.saturating_sub(rest_max));
+        let max = (max as u32).min(remaining.saturating_sub(rest_min));
+        (min <= max && remaining >= rest_min).then_some((min as u16, max as u16))
+    }
+
+    /// The `log2` of the total weight of the counts of the stage `j` (and later stages)
+    /// that fill the `remaining` length, if it is already computed.
+    fn cached_total(&self, j: usize, remaining: u16) -> f64 {
+        let total = if j + 1 == self.stages.len() {
+            self.weights[j][remaining as usize]
+        } else {
+            self.totals[j][remaining as usize]
+        };
+        total + self.log_first(j, remaining)
+    }
+
+    /// The `log2` of the weight of picking `count` characters for the stage `j`
+    /// (relative to the `remaining` length).
+    fn log_weight(&mut self, j: usize, remaining: u16, count: u16) -> f64 {
+        self.weights[j][count as usize] + self.log_total(j + 1, remaining - count)
+    }
+
+    /// The `log2` of the total weight of the counts of the stage `j` (and later stages)
+    /// that fill the `remaining` length.
+    fn log_total(&mut self, j: usize, remaining: u16) -> f64 {
+        if j + 1 == self.stages.len() || !self.totals[j][remaining as usize].is_nan() {
+            return self.cached_total(j, remaining);
+        }
+        let Some((min, max)) = self.choices(j, remaining) else {
+            self.totals[j][remaining as usize] = f64::NEG_INFINITY;
+            return f64::NEG_INFINITY;
+        };
+
+        // the weights are log-concave, so climbing to a more likely count finds the most likely
+        let mut mode = self.modes[j].clamp(min, max);
+        let mut best = self.log_weight(j, remaining, mode);
+        let mut climbed = false;
+        while mode < max {
+            let weight = self.log_weight(j, remaining, mode + 1);
+            if weight <= best {
+                break;
+            }
+            (mode, best, climbed) = (mode + 1, weight, true);
+        }
+        while !climbed && mode > min {
+            let weight = self.log_weight(j, remaining, mode - 1);
+            if weight <= best {
+                break;
+            }
+            (mode, best) = (mode - 1, weight);
+        }
+        self.modes[j] = mode;
+
+        // sum the non-negligible weights around the most likely count
+        let (mut low, mut high, mut sum) = (mode, mode, 1.0);
+        if best.is_finite() {
+            while low > min {
+                let weight = self.log_weight(j, remaining, low - 1) - best;
+                if weight < -NEGLIGIBLE_BITS {
+                    break;
+                }
+                (low, sum) = (low - 1, sum + weight.exp2());
+            }
+            while high < max {
+                let weight = self.log_weight(j, remaining, high + 1) - best;
+                if weight < -NEGLIGIBLE_BITS {
+                    break;
+                }
+                (high, sum) = (high + 1, sum + weight.exp2());
+            }
+        }
+        self.totals[j][remaining as usize] = best + sum.log2();
+        self.windows[j][remaining as usize] = (low, high);
+        self.cached_total(j, remaining)
+    }
+
+    /// Find the most likely combination of counts (ignoring which kind of character is first).
+    ///
+    /// Each unit of length is greedily given to the kind of character
+    /// that gains the most weight from it.
+    fn joint_mode(&self) -> Vec<u16> {
+        let mut counts = self
+            .stages
+            .iter()
+            .map(|&(_, min, _)| min)
+            .collect::<Vec<_>>();
+        let mut remaining = (self.config.length as u32).saturating_sub(self.rest[0].0);
+        while remaining > 0 {
+            let gain = |j: usize| {
+                let count = counts[j] as usize;
+                self.weights[j][count + 1] - self.weights[j][count]
+            };
+            let Some(j) = (0..counts.len())
+                .filter(|&j| counts[j] < self.stages[j].2.min(self.config.length))
+                .max_by(|&a, &b| gain(a).total_cmp(&gain(b)))
+            else {
+                break;
+            };
+            counts[j] += 1;
+            remaining -= 1;
+        }
+        counts
+    }
+}
+
+impl CharacterSets {
+    /// The `log2` of the number of ways to choose `count` characters of the given `stage`,
+    /// divided by `count!` (the number of ways to arrange them among themselves).
+    fn log_count(
+        &self,
+        config: PasswordRequirements,
+        stage: Stage,
+        count: u16,
+        factorials: &Log2Factorials,
+    ) -> f64 {
+        let set = match stage {
+            Stage::Decimal => self.decimal(),
+            Stage::Special => self.special(),
+            Stage::Uppercase => self.uppercase(),
+            Stage::Lowercase => self.lowercase(),
+            Stage::Letters => {
+                let (upper, lower) = self.split_letters(config, count);
+                return self.log_count(config, Stage::Uppercase, upper, factorials)
+                    + self.log_count(config, Stage::Lowercase, lower, factorials);
+            }
+        };
+        let characters = if count == 0 {
+            0.0
+        } else if config.allow_repeats {
+            count as f64 * (set.len() as f64).log2()
+        } else {
+            factorials.permutations(set.len(), count as usize)
+        };
+        characters - factorials.factorial(count as usize)
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{CharacterSets, PasswordRequirements, counts::CountDistribution};
+
+/// Calculate `log2(n!)`.
+pub(crate) fn log2_factorial(n: u16) -> f64 {
+    (2..=n as u32).map(|i| (i as f64).log2()).sum()
+}
+
+/// A table of `log2(n!)` for each `n` up to a maximum.
+///
+/// This avoids recalculating the same sums for each count of each kind of character.
+pub(crate) struct Log2Factorials(Vec<f64>);
+
+impl Log2Factorials {
+    /// Create a table for each `n` in the range `0..=max`.
+    pub(crate) fn new(max: usize) -> Self {
+        let mut table = Vec::with_capacity(max + 1);
+        let mut sum = 0.0;
+        table.push(sum);
+        for i in 1..=max {
+            sum += (i as f64).log2();
+            table.push(sum);
+        }
+        Self(table)
+    }
+
+    /// Get `log2(n!)`.
+    pub(crate) fn factorial(&self, n: usize) -> f64 {
+        self.0[n]
+    }
+
+    /// Get `log2(n! / (n - k)!)`, the number of ways to pick `k` distinct items from `n` items.
+    pub(crate) fn permutations(&self, n: usize, k: usize) -> f64 {
+        self.0[n] - self.0[n - k]
+    }
+}
+
+/// Calculate the theoretical entropy (in bits) of a password generated with the given `config`.
+///
+/// This function will invoke [`PasswordRequirements::validate()`] to
+/// ensure basic password requirements are met.
+/// Use [`CharacterSets::password_entropy()`] for custom character sets.
+///
+/// The entropy is the base 2 logarithm of the number of possible passwords
+/// that satisfy the `config`. This accounts for
+///
+/// - the number of characters of each kind (see [`PasswordRequirements::decimal`]
+///   and [`PasswordRequirements::specials`]),
+/// - whether the first character is restricted to only letters, and
+/// - whether characters may be used more than once.
+///
+/// If a kind of character has a range of counts (see [`PasswordRequirements::max_decimal`]),
+/// then this also accounts for each combination of counts.
+/// Since every possible password is equally likely to be generated,
+/// this is also the Shannon entropy of the generated passwords.
+///
+/// ```rust
+/// use mk_pass::{PasswordRequirements, password_entropy};
+/// let config = PasswordRequirements::default();
+/// let bits = password_entropy(config);
+/// assert_eq!(format!("{bits:.2}"), "90.03");
+/// ```
+pub fn password_entropy(config: PasswordRequirements) -> f64 {
+    CharacterSets::default().password_entropy(config)
+}
+
+impl CharacterSets {
+    /// Calculate the theoretical entropy (in bits) of a password generated
+    /// from these character sets with the given `config`.
+    ///
+    /// This function will invoke [`PasswordRequirements::validate_with()`] to
+    /// ensure basic password requirements are met.
+    /// See also [`password_entropy()`].
+    pub fn password_entropy(&self, config: PasswordRequirements) -> f64 {
+        CountDistribution::new(self, config.validate_with(self)).entropy()
+    }
+
+    /// Create the table of factorials needed to weigh the counts of each kind of character
+    /// for a validated `config`.
+    pub(crate) fn log2_factorials(&self, config: PasswordRequirements) -> Log2Factorials {
+        let mut max = config.length as usize;
+        if !config.allow_repeats {
+            for set in [
+                self.uppercase(),
+                self.lowercase(),
+                self.decimal(),
+                self.special(),
+            ] {
+                max = max.max(set.len());
+            }
+        }
+        Log2Factorials::new(max)
+    }
+}
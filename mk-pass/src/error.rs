@@ -0,0 +1,272 @@
+use std::fmt::Display;
+#[cfg(feature = "policy")]
+use std::path::PathBuf;
+
+/// A violated constraint found by [`PasswordRequirements::check()`](crate::PasswordRequirements::check).
+///
+/// Each variant describes a value that [`PasswordRequirements::validate()`](crate::PasswordRequirements::validate)
+/// would otherwise change silently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequirementsError {
+    /// The character sets do not provide any letters.
+    NoLetters,
+
+    /// The `length` is less than the `minimum` length.
+    TooShort { length: u16, minimum: u16 },
+
+    /// The `length` is more than the `maximum` length that the character sets and
+    /// the (maximum) counts of each kind of character can fill.
+    TooLong { length: u16, maximum: u16 },
+
+    /// The number of `decimal` integers overrules the required letters and special characters.
+    DecimalOverrulesOthers { decimal: u16, maximum: u16 },
+
+    /// The number of `specials` characters overrules the required letters and decimal integers.
+    SpecialsOverruleOthers { specials: u16, maximum: u16 },
+
+    /// The number of `uppercase` letters overrules the required lowercase letters and other characters.
+    UppercaseOverrulesOthers { uppercase: u16, maximum: u16 },
+
+    /// The number of `lowercase` letters overrules the required uppercase letters and other characters.
+    LowercaseOverrulesOthers { lowercase: u16, maximum: u16 },
+
+    /// The number of `decimal` integers is more than the `maximum` possible without repetitions.
+    TooManyDecimal { decimal: u16, maximum: u16 },
+
+    /// The number of `specials` characters is more than the `maximum` possible without repetitions.
+    TooManySpecials { specials: u16, maximum: u16 },
+
+    /// The `maximum` count of the kind of characters `name`d is less than its `minimum` count.
+    MaximumLessThanMinimum {
+        name: &'static str,
+        minimum: u16,
+        maximum: u16,
+    },
+
+    /// The number of `uppercase` letters is more than the `maximum` possible without repetitions.
+    TooManyUppercase { uppercase: u16, maximum: u16 },
+
+    /// The number of `lowercase` letters is more than the `maximum` possible without repetitions.
+    TooManyLowercase { lowercase: u16, maximum: u16 },
+
+    /// The lowercase letters do not include a single-letter consonant and vowel,
+    /// so pronounceable passwords cannot be generated.
+    NotPronounceable,
+
+    /// The maximum counts of uppercase and lowercase letters allow only `letters` letters,
+    /// but at least `minimum` letters are needed for the first character or the minimum length.
+    TooFewLetters { letters: u16, minimum: u16 },
+
+    /// The set of characters `name`d has `size` characters,
+    /// which is more than the [`u16::MAX`] characters that any count can use.
+    SetTooLarge { name: &'static str, size: usize },
+}
+
+impl Display for RequirementsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoLetters => write!(f, "No letters are available"),
+            Self::TooShort { length, minimum } => {
+                write!(f, "A length of {length} is less than the minimum {minimum}")
+            }
+            Self::TooLong { length, maximum } => write!(
+                f,
+                "A length of {length} is more than the maximum {maximum} that the character sets and counts can fill"
+            ),
+            Self::DecimalOverrulesOthers { decimal, maximum } => write!(
+                f,
+                "{decimal} decimal integers overrule the required letters and special characters \
+                (maximum is {maximum})"
+            ),
+            Self::SpecialsOverruleOthers { specials, maximum } => write!(
+                f,
+                "{specials} special characters overrule the required letters and decimal integers \
+                (maximum is {maximum})"
+            ),
+            Self::UppercaseOverrulesOthers { uppercase, maximum } => write!(
+                f,
+                "{uppercase} uppercase letters overrule the required lowercase letters and other characters \
+                (maximum is {maximum})"
+            ),
+            Self::LowercaseOverrulesOthers { lowercase, maximum } => write!(
+                f,
+                "{lowercase} lowercase letters overrule the required uppercase letters and other characters \
+                (maximum is {maximum})"
+            ),
+            Self::TooManyDecimal { decimal, maximum } => write!(
+                f,
+                "{decimal} decimal integers is more than the maximum {maximum} without repeated characters"
+            ),
+            Self::TooManySpecials { specials, maximum } => write!(
+                f,
+                "{specials} special characters is more than the maximum {maximum} without repeated characters"
+            ),
+            Self::MaximumLessThanMinimum {
+                name,
+                minimum,
+                maximum,
+            } => write!(
+                f,
+                "The maximum {maximum} {name} characters is less than the minimum {minimum}"
+            ),
+            Self::TooManyUppercase { uppercase, maximum } => write!(
+                f,
+                "{uppercase} uppercase letters is more than the maximum {maximum} without repeated characters"
+            ),
+            Self::TooManyLowercase { lowercase, maximum } => write!(
+                f,
+                "{lowercase} lowercase letters is more than the maximum {maximum} without repeated characters"
+            ),
+            Self::NotPronounceable => write!(
+                f,
+                "Pronounceable passwords need at least one lowercase consonant and one lowercase vowel"
+            ),
+            Self::TooFewLetters { letters, minimum } => write!(
+                f,
+                "The maximum counts of letters allow {letters} letters, but at least {minimum} are needed"
+            ),
+            Self::SetTooLarge { name, size } => write!(
+                f,
+                "The {size} {name} characters are more than the maximum {}",
+                u16::MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RequirementsError {}
+
+/// An error found when loading a [`Policy`](crate::Policy).
+#[cfg(feature = "policy")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// The file at `path` could not be read.
+    Read { path: PathBuf, message: String },
+
+    /// The format of the file at `path` is not recognized by its extension.
+    UnknownFormat { path: PathBuf },
+
+    /// The policy could not be parsed.
+    Parse { message: String },
+
+    /// The profile `name`d is not one of the `available` profiles.
+    UnknownProfile {
+        name: String,
+        available: Vec<String>,
+    },
+
+    /// No profile was named, and the policy does not have a default profile
+    /// among its `available` profiles.
+    NoDefaultProfile { available: Vec<String> },
+}
+
+#[cfg(feature = "policy")]
+impl Display for PolicyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read { path, message } => {
+                write!(f, "Failed to read {}: {message}", path.display())
+            }
+            Self::UnknownFormat { path } => write!(
+                f,
+                "Unknown policy format of {} (expected a .toml, .json, .yaml, or .yml file)",
+                path.display()
+            ),
+            Self::Parse { message } => write!(f, "Failed to parse policy: {message}"),
+            Self::UnknownProfile { name, available } => write!(
+                f,
+                "Unknown profile '{name}' (available profiles are: {})",
+                available.join(", ")
+            ),
+            Self::NoDefaultProfile { available } => write!(
+                f,
+                "No profile was specified and there is no '{}' profile (available profiles are: {})",
+                crate::Policy::DEFAULT_PROFILE,
+                available.join(", ")
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "policy")]
+impl std::error::Error for PolicyError {}
+
+/// An error found when parsing [`PasswordRules`](crate::PasswordRules).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordRulesError {
+    /// The `clause` is not a `name: value` pair.
+    MissingValue { clause: String },
+
+    /// The property `name` is not supported.
+    UnknownProperty { name: String },
+
+    /// The character class `name` is not supported.
+    UnknownClass { name: String },
+
+    /// The character `ch` in a custom character class is not ASCII printable.
+    UnsupportedCharacter { ch: char },
+
+    /// A custom character class is missing its closing `]`.
+    UnterminatedClass { class: String },
+
+    /// The `value` of the `property` is not a positive integer.
+    InvalidNumber { property: String, value: String },
+
+    /// The `minimum` length (at least the minimum length of any password)
+    /// is more than the `maximum` length.
+    InvalidLength { minimum: u16, maximum: u16 },
+
+    /// The `minimum` length is more than the `maximum` length of a password
+    /// that satisfies the rules (for example, without repeating any allowed character).
+    UnreachableLength { minimum: u16, maximum: u16 },
+
+    /// A `required` `class` only covers some of the allowed characters of its kind,
+    /// which cannot be expressed with [`PasswordRequirements`](crate::PasswordRequirements).
+    UnsupportedRequirement { class: String },
+
+    /// The rules do not allow any letters.
+    NoLetters,
+}
+
+impl Display for PasswordRulesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingValue { clause } => {
+                write!(f, "The clause '{clause}' is not a 'name: value' pair")
+            }
+            Self::UnknownProperty { name } => write!(f, "Unsupported property '{name}'"),
+            Self::UnknownClass { name } => write!(f, "Unsupported character class '{name}'"),
+            Self::UnsupportedCharacter { ch } => write!(
+                f,
+                "The character {ch:?} in a custom character class is not ASCII printable"
+            ),
+            Self::UnterminatedClass { class } => {
+                write!(
+                    f,
+                    "The custom character class '{class}' is missing a closing ']'"
+                )
+            }
+            Self::InvalidNumber { property, value } => {
+                write!(
+                    f,
+                    "The value '{value}' of '{property}' is not a positive integer"
+                )
+            }
+            Self::InvalidLength { minimum, maximum } => write!(
+                f,
+                "The minimum length {minimum} is more than the maximum length {maximum}"
+            ),
+            Self::UnreachableLength { minimum, maximum } => write!(
+                f,
+                "The minimum length {minimum} is more than the {maximum} characters the rules allow"
+            ),
+            Self::UnsupportedRequirement { class } => write!(
+                f,
+                "The required class '{class}' only covers some of the allowed characters of its kind"
+            ),
+            Self::NoLetters => write!(f, "The rules do not allow any letters"),
+        }
+    }
+}
+
+impl std::error::Error for PasswordRulesError {}
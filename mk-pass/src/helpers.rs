@@ -18,6 +18,9 @@ pub const LOWERCASE: [char; 26] = [
     't', 'u', 'v', 'w', 'x', 'y', 'z',
 ];
 
+/// The list of characters that are easily mistaken for one another when read by a person.
+pub const AMBIGUOUS_CHARACTERS: [char; 7] = ['0', 'O', 'o', '1', 'I', 'l', '|'];
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum CharKind {
     Uppercase,
@@ -26,25 +29,7 @@ pub enum CharKind {
     Special,
 }
 
-impl CharKind {
-    pub fn into_sample(self) -> &'static [char] {
-        match self {
-            CharKind::Uppercase => &UPPERCASE,
-            CharKind::Lowercase => &LOWERCASE,
-            CharKind::Decimal => &DECIMAL,
-            CharKind::Special => &SPECIAL_CHARACTERS,
-        }
-    }
-
-    pub fn pop_kind(available: Vec<Self>, kind: &Self) -> Vec<Self> {
-        available
-            .iter()
-            .filter_map(|v| if *v == *kind { None } else { Some(*v) })
-            .collect::<Vec<CharKind>>()
-    }
-}
-
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct CountTypesUsed {
     pub uppercase: u16,
     pub lowercase: u16,
@@ -7,150 +7,350 @@
 )]
 use rand::prelude::*;
 mod helpers;
-use helpers::{CharKind, CountTypesUsed};
-pub use helpers::{DECIMAL, LOWERCASE, SPECIAL_CHARACTERS, UPPERCASE};
+use helpers::CharKind;
+pub use helpers::{AMBIGUOUS_CHARACTERS, DECIMAL, LOWERCASE, SPECIAL_CHARACTERS, UPPERCASE};
 mod config;
 pub use config::PasswordRequirements;
+mod alphabet;
+pub use alphabet::Alphabet;
+mod charset;
+pub use charset::CharacterSets;
+use charset::set_len;
+mod error;
+#[cfg(feature = "policy")]
+pub use error::PolicyError;
+pub use error::{PasswordRulesError, RequirementsError};
+mod counts;
+use counts::CountDistribution;
+mod entropy;
+pub use entropy::password_entropy;
+mod batch;
+pub use batch::{Passwords, generate_passwords, generate_passwords_with_rng};
+mod analyzer;
+pub use analyzer::{PasswordAnalysis, analyze_password};
+mod preset;
+pub use preset::Preset;
+mod rules;
+pub use rules::PasswordRules;
+#[cfg(feature = "policy")]
+mod policy;
+#[cfg(feature = "policy")]
+pub use policy::{Policy, PolicyFormat};
+mod pronounceable;
+pub use pronounceable::{
+    generate_pronounceable_password, generate_pronounceable_password_with_rng,
+    pronounceable_entropy,
+};
+mod pin;
+pub use pin::{PinPattern, PinRequirements, generate_pin, generate_pin_with_rng, weak_pin_pattern};
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "zeroize")]
+pub use secret::{SecretString, generate_secret_password, generate_secret_password_with_rng};
+mod passphrase;
+pub use passphrase::{
+    Capitalization, PassphraseRequirements, Wordlist, generate_passphrase,
+    generate_passphrase_with_rng,
+};
+
+#[cfg(feature = "clap")]
+mod cli;
+#[cfg(all(feature = "clap", feature = "policy"))]
+pub use cli::PolicyArgs;
+#[cfg(feature = "clap")]
+pub use cli::{CheckArgs, Cli, Command, PassphraseArgs, PinArgs};
 
 #[cfg(feature = "clap")]
 pub use clap;
 
+pub use rand;
+
 /// Generate a password given the constraints specified by `config`.
 ///
 /// This function will invoke [`PasswordRequirements::validate()`] to
 /// ensure basic password requirements are met.
+///
+/// Every password that satisfies the `config` is equally likely to be generated.
+/// Note that if neither [`PasswordRequirements::max_uppercase`] nor
+/// [`PasswordRequirements::max_lowercase`] is specified, then the letters are split
+/// evenly between uppercase and lowercase letters, so a password with any other
+/// number of uppercase letters is never generated.
+///
+/// Use [`CharacterSets::generate_password()`] to generate a password from custom character sets.
 pub fn generate_password(config: PasswordRequirements) -> String {
-    let config = config.validate();
-    let len = config.length as usize;
-    let mut rng = rand::rng();
-    let mut password = String::with_capacity(len);
-
-    let mut used_types = CountTypesUsed::default();
-    let mut available_types = vec![CharKind::Uppercase, CharKind::Lowercase];
-    if config.specials > 0 {
-        available_types.push(CharKind::Special);
-    }
-    if config.decimal > 0 {
-        available_types.push(CharKind::Decimal);
-    }
-    let max_letters = len as u16 - config.decimal - config.specials;
-    let max_lowercase = max_letters / 2;
-    let max_uppercase = max_letters - max_lowercase;
-    #[cfg(test)]
-    {
-        println!("max_lowers: {max_lowercase}, max_uppers: {max_uppercase}");
-    }
-
-    let mut pass_chars = vec!['\n'; len];
-
-    let start = if config.first_is_letter {
-        let letter_types = [CharKind::Lowercase, CharKind::Uppercase];
-        let sample_kind = letter_types[rng.random_range(0..letter_types.len())];
-        let sample_set = sample_kind.into_sample();
-        pass_chars[0] = sample_set[rng.random_range(0..LOWERCASE.len())];
-        match sample_kind {
-            CharKind::Lowercase => {
-                used_types.lowercase += 1;
-                if used_types.lowercase == max_lowercase {
-                    available_types = CharKind::pop_kind(available_types, &sample_kind);
-                }
-            }
-            _ => {
-                used_types.uppercase += 1;
-                if used_types.uppercase == max_uppercase {
-                    available_types = CharKind::pop_kind(available_types, &sample_kind);
-                }
-            }
-        }
-        1
-    } else {
-        0
-    };
-    let mut positions = (start..len).collect::<Vec<usize>>();
-
-    for _ in start..len {
-        debug_assert!(!available_types.is_empty());
-        // pick a sample set from which to pick a character
-        let kind = available_types[rng.random_range(0..available_types.len())];
-        match kind {
-            CharKind::Lowercase => {
-                used_types.lowercase += 1;
-                #[cfg(test)]
-                {
-                    println!("used lowers: {}", used_types.lowercase);
-                }
-                if used_types.lowercase == max_lowercase {
-                    available_types = CharKind::pop_kind(available_types, &kind);
-                }
-            }
-            CharKind::Uppercase => {
-                used_types.uppercase += 1;
-                #[cfg(test)]
-                {
-                    println!("used uppers: {}", used_types.uppercase);
-                }
-                if used_types.uppercase == max_uppercase {
-                    available_types = CharKind::pop_kind(available_types, &kind);
-                }
-            }
-            CharKind::Decimal => {
-                used_types.number += 1;
-                if used_types.number == config.decimal {
-                    available_types = CharKind::pop_kind(available_types, &kind);
-                }
-            }
-            CharKind::Special => {
-                used_types.special += 1;
-                if used_types.special == config.specials {
-                    available_types = CharKind::pop_kind(available_types, &kind);
-                }
+    CharacterSets::default().generate_password(config)
+}
+
+/// Generate a password given the constraints specified by `config` using the given `rng`.
+///
+/// This behaves like [`generate_password()`], but all randomness is drawn from the given `rng`.
+/// Thus, a seeded `rng` can be used to reproduce the same password (useful for testing).
+///
+/// ```rust
+/// use mk_pass::{PasswordRequirements, generate_password_with_rng};
+/// use mk_pass::rand::{SeedableRng, rngs::StdRng};
+///
+/// let config = PasswordRequirements::default();
+/// let password = generate_password_with_rng(config, &mut StdRng::seed_from_u64(42));
+/// let reproduced = generate_password_with_rng(config, &mut StdRng::seed_from_u64(42));
+/// assert_eq!(password, reproduced);
+/// ```
+pub fn generate_password_with_rng<R: CryptoRng + Rng>(
+    config: PasswordRequirements,
+    rng: &mut R,
+) -> String {
+    CharacterSets::default().generate_password_with_rng(config, rng)
+}
+
+/// Generate a password given the constraints specified by `config`.
+///
+/// Unlike [`generate_password()`], this function does not change the `config`.
+/// Instead, it returns an error if [`PasswordRequirements::check()`] finds that
+/// the `config` does not satisfy basic password requirements.
+///
+/// ```rust
+/// use mk_pass::{PasswordRequirements, RequirementsError, try_generate_password};
+/// let req = PasswordRequirements {
+///     length: 16,
+///     specials: 16,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     try_generate_password(req),
+///     Err(RequirementsError::SpecialsOverruleOthers { specials: 16, maximum: 13 })
+/// );
+/// ```
+pub fn try_generate_password(config: PasswordRequirements) -> Result<String, RequirementsError> {
+    CharacterSets::default().try_generate_password(config)
+}
+
+impl CharacterSets {
+    /// Generate a password from these character sets given the constraints specified by `config`.
+    ///
+    /// This function will invoke [`PasswordRequirements::check_with()`] and return any error found.
+    /// See also [`try_generate_password()`].
+    pub fn try_generate_password(
+        &self,
+        config: PasswordRequirements,
+    ) -> Result<String, RequirementsError> {
+        config.check_with(self)?;
+        Ok(self.generate_password(config))
+    }
+
+    /// Generate a password from these character sets given the constraints specified by `config`.
+    ///
+    /// This function will invoke [`PasswordRequirements::validate_with()`] to
+    /// ensure basic password requirements are met.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both the [`CharacterSets::uppercase()`] and [`CharacterSets::lowercase()`]
+    /// sets are empty.
+    pub fn generate_password(&self, config: PasswordRequirements) -> String {
+        self.generate_password_with_rng(config, &mut rand::rng())
+    }
+
+    /// Generate a password from these character sets given the constraints specified by `config`
+    /// using the given `rng`.
+    ///
+    /// See also [`generate_password_with_rng()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if both the [`CharacterSets::uppercase()`] and [`CharacterSets::lowercase()`]
+    /// sets are empty.
+    pub fn generate_password_with_rng<R: CryptoRng + Rng>(
+        &self,
+        config: PasswordRequirements,
+        rng: &mut R,
+    ) -> String {
+        self.generate_validated_password(config.validate_with(self), rng)
+    }
+
+    /// Generate a password from these character sets given an already validated `config`.
+    ///
+    /// See [`CharacterSets::generate_counted_password()`].
+    pub(crate) fn generate_validated_password<R: CryptoRng + Rng>(
+        &self,
+        config: PasswordRequirements,
+        rng: &mut R,
+    ) -> String {
+        self.generate_counted_password(config, &CountDistribution::new(self, config), rng)
+    }
+
+    /// Generate a password from these character sets given an already validated `config`
+    /// and its `distribution` of counts.
+    ///
+    /// Every password that satisfies the `config` is equally likely:
+    ///
+    /// 1. The count of each kind of character is [picked](CountDistribution::pick())
+    ///    with a probability proportional to the number of passwords with those counts.
+    /// 2. The characters of each kind are picked uniformly (and distinct from one another
+    ///    unless [`PasswordRequirements::allow_repeats`] is enabled).
+    /// 3. The characters are shuffled uniformly (with a letter first if
+    ///    [`PasswordRequirements::first_is_letter`] is enabled).
+    ///
+    /// This takes time linear in the length of the password.
+    pub(crate) fn generate_counted_password<R: CryptoRng + Rng>(
+        &self,
+        config: PasswordRequirements,
+        distribution: &CountDistribution,
+        rng: &mut R,
+    ) -> String {
+        let len = config.length as usize;
+        let counts = distribution.pick(rng);
+        let letters = counts.uppercase + counts.lowercase;
+        assert!(
+            !config.first_is_letter || letters > 0,
+            "The uppercase and lowercase character sets cannot both be empty"
+        );
+
+        // pick the characters of each kind (grouped by kind, so the letters come first)
+        let mut pass_chars = Vec::with_capacity(len);
+        let mut pool = Vec::new();
+        for (kind, count) in [
+            (CharKind::Uppercase, counts.uppercase),
+            (CharKind::Lowercase, counts.lowercase),
+            (CharKind::Decimal, counts.number),
+            (CharKind::Special, counts.special),
+        ] {
+            let set = self.get(kind);
+            if config.allow_repeats {
+                pass_chars.extend((0..count).map(|_| set[rng.random_range(0..set.len())]));
+            } else {
+                pool.clear();
+                pool.extend_from_slice(set);
+                let (chosen, _) = pool.partial_shuffle(rng, count as usize);
+                pass_chars.extend_from_slice(chosen);
             }
         }
 
-        // now generate character from selected sample set
-        let sample = kind.into_sample();
-        let mut rand_index = rng.random_range(0..sample.len());
-        if !config.allow_repeats {
-            while pass_chars.contains(&sample[rand_index]) {
-                rand_index = rng.random_range(0..sample.len());
-            }
+        // arrange the characters with a Fisher-Yates shuffle; the first character is a letter
+        // picked uniformly from all the letters, so every arrangement is equally likely
+        let start = if config.first_is_letter {
+            pass_chars.swap(0, rng.random_range(0..letters as usize));
+            1
+        } else {
+            0
+        };
+        pass_chars[start..].shuffle(rng);
+
+        // allocate the exact number of bytes, so no (unwiped) reallocation is left behind
+        let mut password = String::with_capacity(pass_chars.iter().map(|ch| ch.len_utf8()).sum());
+        password.extend(pass_chars.iter());
+        #[cfg(feature = "zeroize")]
+        {
+            zeroize::Zeroize::zeroize(&mut pass_chars);
+            zeroize::Zeroize::zeroize(&mut pool);
         }
+        password
+    }
 
-        // now pick an index in the password that hasn't been used
-        let rnd_pos = rng.random_range(0..positions.len());
-        let pos = positions.remove(rnd_pos);
-        pass_chars[pos] = sample[rand_index];
+    /// The range of counts (in the order of decimal integers, special characters,
+    /// uppercase letters, and lowercase letters) allowed by a validated `config`.
+    pub(crate) fn count_ranges(&self, config: PasswordRequirements) -> [(u16, u16); 4] {
+        let range = |minimum: u16, maximum: Option<u16>, set: &[char]| {
+            let maximum = if set.is_empty() {
+                0
+            } else if config.allow_repeats {
+                maximum.unwrap_or(u16::MAX)
+            } else {
+                maximum.unwrap_or(u16::MAX).min(set_len(set))
+            };
+            (minimum.min(maximum), maximum)
+        };
+        [
+            range(
+                config.decimal,
+                config.max_decimal.or(Some(config.decimal)),
+                self.decimal(),
+            ),
+            range(
+                config.specials,
+                config.max_specials.or(Some(config.specials)),
+                self.special(),
+            ),
+            range(
+                config.uppercase.max(1),
+                config.max_uppercase,
+                self.uppercase(),
+            ),
+            range(
+                config.lowercase.max(1),
+                config.max_lowercase,
+                self.lowercase(),
+            ),
+        ]
+    }
+
+    /// Are the letters [split](CharacterSets::split_letters()) (instead of picked)
+    /// for a validated `config`?
+    pub(crate) fn splits_letters(&self, config: PasswordRequirements) -> bool {
+        config.max_uppercase.is_none() && config.max_lowercase.is_none()
     }
 
-    for ch in pass_chars {
-        password.push(ch);
+    /// Split the number of `letters` into a number of uppercase and lowercase letters
+    /// as specified by a validated `config`.
+    ///
+    /// The letters are split evenly, unless the `config` requires more of either kind
+    /// or a set is too small to provide its share (without repetitions).
+    pub(crate) fn split_letters(&self, config: PasswordRequirements, letters: u16) -> (u16, u16) {
+        let max_of = |set: &[char]| {
+            if set.is_empty() {
+                0
+            } else if config.allow_repeats {
+                u16::MAX
+            } else {
+                set_len(set)
+            }
+        };
+        let max_upper = max_of(self.uppercase());
+        let max_lower = max_of(self.lowercase());
+        let mut lower = letters / 2;
+        let mut upper = letters - lower;
+        if upper < config.uppercase {
+            upper = config.uppercase;
+            lower = letters - upper;
+        }
+        if lower < config.lowercase {
+            lower = config.lowercase;
+            upper = letters - lower;
+        }
+        if upper > max_upper {
+            lower += upper - max_upper;
+            upper = max_upper;
+        }
+        if lower > max_lower {
+            upper += lower - max_lower;
+            lower = max_lower;
+        }
+        (upper, lower)
     }
-    password
 }
 
 #[cfg(test)]
 mod test {
-    use super::{PasswordRequirements, generate_password};
-    use crate::helpers::{DECIMAL, LOWERCASE, SPECIAL_CHARACTERS, UPPERCASE};
+    use super::{
+        Capitalization, CharacterSets, CountDistribution, PassphraseRequirements,
+        PasswordRequirements, RequirementsError, Wordlist, analyze_password, generate_passphrase,
+        generate_passphrase_with_rng, generate_password, generate_password_with_rng,
+        generate_passwords, generate_passwords_with_rng, generate_pronounceable_password,
+        generate_pronounceable_password_with_rng, password_entropy, pronounceable_entropy,
+        try_generate_password,
+    };
+    use crate::helpers::{AMBIGUOUS_CHARACTERS, DECIMAL, LOWERCASE, SPECIAL_CHARACTERS, UPPERCASE};
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+    use std::collections::HashSet;
 
     fn count(output: &str) -> (usize, usize, usize, usize, usize) {
-        let (mut uppers, mut lowers, mut decimal, mut specials) = (0, 0, 0, 0);
-        let mut repeats = vec![];
-        for (i, ch) in output.char_indices() {
-            if LOWERCASE.contains(&ch) {
-                lowers += 1;
-            } else if UPPERCASE.contains(&ch) {
-                uppers += 1;
-            } else if DECIMAL.contains(&ch) {
-                decimal += 1;
-            } else if SPECIAL_CHARACTERS.contains(&ch) {
-                specials += 1;
-            }
-            if output[0..i].contains(ch) && !repeats.contains(&ch) {
-                repeats.push(ch);
-            }
-        }
-        let repeats = repeats.len();
+        let analysis = analyze_password(output);
+        let (uppers, lowers, decimal, specials) = (
+            analysis.uppercase,
+            analysis.lowercase,
+            analysis.decimal,
+            analysis.specials,
+        );
+        let repeats = analysis.repeated.len();
         println!(
             "decimal: {decimal}, uppercase: {uppers}, lowercase: {lowers}, special: {specials}, repeats: {repeats}"
         );
@@ -222,14 +422,13 @@ mod test {
         gen_pass(config);
     }
 
-    /// This is a hacky way to ensure complete coverage about the first character kind.
+    /// Ensure complete coverage about the first character kind.
     ///
     /// It basically keeps generating a password until the first random letter is either
     /// [`UPPERCASE`] or [`LOWERCASE`] as specified by the `lower` parameter.
     ///
-    /// Theoretically, this could cause an infinite loop or just run a long time because
-    /// we are relying on randomness. However, the condition being tested means the
-    /// probability of the randomness is 50:50.
+    /// A seeded RNG is used, so the number of iterations is deterministic.
+    /// The condition being tested means the probability of the randomness is 50:50.
     fn till_first_is(lower: bool) {
         let config = PasswordRequirements {
             specials: 8,
@@ -238,10 +437,11 @@ mod test {
             ..Default::default()
         };
         let sample_set: &[char] = if lower { &LOWERCASE } else { &UPPERCASE };
+        let mut rng = StdRng::seed_from_u64(0);
 
-        let mut password = generate_password(config);
+        let mut password = generate_password_with_rng(config, &mut rng);
         while !sample_set.contains(&password.chars().next().unwrap()) {
-            password = generate_password(config);
+            password = generate_password_with_rng(config, &mut rng);
         }
     }
 
@@ -254,4 +454,1467 @@ mod test {
     fn gen_first_upper() {
         till_first_is(false);
     }
+
+    #[test]
+    fn custom_special_set() {
+        let sets = CharacterSets::default().with_special(['-', '_', '.', '-']);
+        assert_eq!(sets.special(), &['-', '_', '.']);
+        let config = PasswordRequirements {
+            specials: 4,
+            ..Default::default()
+        }
+        .validate_with(&sets);
+        assert_eq!(config.specials, 3);
+        let password = sets.generate_password(config);
+        println!("Generated password: {password}");
+        assert_eq!(password.len(), config.length as usize);
+        let specials = password.chars().filter(|ch| sets.special().contains(ch));
+        assert_eq!(specials.count(), config.specials as usize);
+        assert!(!password.contains(|ch| SPECIAL_CHARACTERS.contains(&ch) && !"-_.".contains(ch)));
+    }
+
+    #[test]
+    fn small_uppercase_set() {
+        let sets = CharacterSets::default().with_uppercase(['X', 'Y']);
+        let config = PasswordRequirements {
+            length: 100,
+            ..Default::default()
+        };
+        let validated = config.validate_with(&sets);
+        assert_eq!(validated.length, 30);
+        let password = sets.generate_password(config);
+        println!("Generated password: {password}");
+        let (uppers, lowers, decimal, specials, repeats) = count(&password);
+        assert_eq!(uppers, 2);
+        assert!(password.contains('X') && password.contains('Y'));
+        assert_eq!(lowers, LOWERCASE.len());
+        assert_eq!(decimal + specials, 2);
+        assert_eq!(repeats, 0);
+    }
+
+    #[test]
+    fn exclude_ambiguous() {
+        let sets = CharacterSets::default()
+            .without_ambiguous()
+            .exclude(['\\', '/']);
+        assert_eq!(sets.uppercase().len(), UPPERCASE.len() - 2);
+        assert_eq!(sets.lowercase().len(), LOWERCASE.len() - 2);
+        assert_eq!(sets.decimal().len(), DECIMAL.len() - 2);
+        assert_eq!(sets.special().len(), SPECIAL_CHARACTERS.len() - 2);
+        let config = PasswordRequirements {
+            length: 100,
+            decimal: 10,
+            specials: 16,
+            ..Default::default()
+        }
+        .validate_with(&sets);
+        assert_eq!(config.length, 48 + 8 + 14);
+        let password = sets.generate_password(config);
+        println!("Generated password: {password}");
+        assert_eq!(password.len(), config.length as usize);
+        assert!(!password.contains(AMBIGUOUS_CHARACTERS));
+        assert!(!password.contains(['\\', '/']));
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn cli_sets() {
+        use crate::Cli;
+        use clap::Parser;
+
+        let cli = Cli::parse_from(["mk-pass", "-a", "--exclude", "abc"]);
+        let sets = cli.character_sets();
+        assert!(!sets.lowercase().iter().any(|ch| "abcol".contains(*ch)));
+        assert_eq!(sets.lowercase().len(), LOWERCASE.len() - 5);
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn cli_unique_pronounceable() {
+        use crate::Cli;
+        use clap::Parser;
+
+        // only "b" and "a" clusters with 1 of 10 letters capitalized (20 possible passwords)
+        let args = |count: &'static str| {
+            [
+                "mk-pass",
+                "-o",
+                "-u",
+                "-n",
+                count,
+                "-l",
+                "10",
+                "-d",
+                "0",
+                "-s",
+                "0",
+                "-x",
+                "cdefghijklmnopqrstuvwxyz",
+            ]
+        };
+        let cli = Cli::parse_from(args("20"));
+        let sets = cli.character_sets();
+        let passwords = cli.pronounceable_passwords(&sets).collect::<HashSet<_>>();
+        assert_eq!(passwords.len(), 20);
+        // ends early instead of looking for a 21st unique password forever
+        let cli = Cli::parse_from(args("21"));
+        assert_eq!(cli.pronounceable_passwords(&sets).count(), 20);
+    }
+
+    #[test]
+    fn check_matches_validate() {
+        let sets = CharacterSets::default().with_decimal(['0', '1']);
+        for allow_repeats in [false, true] {
+            for length in [0, 9, 10, 16, 54, 56, 70, 78, 100] {
+                for decimal in [0, 1, 2, 8, 13, 20] {
+                    for specials in [0, 1, 5, 16, 20] {
+                        let config = PasswordRequirements {
+                            length,
+                            decimal,
+                            specials,
+                            allow_repeats,
+                            ..Default::default()
+                        };
+                        for sets in [&CharacterSets::default(), &sets] {
+                            let valid = config.validate_with(sets) == config;
+                            assert_eq!(config.check_with(sets).is_ok(), valid, "{config:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn validate_is_idempotent() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let small = CharacterSets::default()
+            .with_uppercase(['A', 'B', 'C'])
+            .with_decimal(['0', '1']);
+        let count = |rng: &mut StdRng| match rng.random_range(0..4) {
+            0 => 0,
+            1 => rng.random_range(1..4),
+            2 => rng.random_range(0..30),
+            _ => rng.random_range(0..80),
+        };
+        let maximum = |rng: &mut StdRng| rng.random_bool(0.5).then(|| count(rng));
+        for _ in 0..5000 {
+            let config = PasswordRequirements {
+                length: rng.random_range(0..100),
+                decimal: count(&mut rng),
+                max_decimal: maximum(&mut rng),
+                specials: count(&mut rng),
+                max_specials: maximum(&mut rng),
+                uppercase: count(&mut rng),
+                max_uppercase: maximum(&mut rng),
+                lowercase: count(&mut rng),
+                max_lowercase: maximum(&mut rng),
+                first_is_letter: rng.random_bool(0.5),
+                allow_repeats: rng.random_bool(0.5),
+            };
+            for sets in [&CharacterSets::default(), &small] {
+                let validated = config.validate_with(sets);
+                assert_eq!(validated.validate_with(sets), validated, "{config:?}");
+                assert_eq!(validated.check_with(sets), Ok(()), "{config:?}");
+                let password = sets.generate_password_with_rng(config, &mut rng);
+                assert_eq!(password.chars().count(), validated.length as usize);
+            }
+            let validated = config.validate();
+            assert!(validated.length >= 10, "{config:?}");
+            let password = generate_password_with_rng(config, &mut rng);
+            assert!(
+                analyze_password(&password).satisfies(validated),
+                "{config:?}"
+            );
+        }
+
+        // no letters are allowed, but the first character is a letter
+        let config = PasswordRequirements {
+            max_uppercase: Some(0),
+            max_lowercase: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.check(),
+            Err(RequirementsError::TooFewLetters {
+                letters: 0,
+                minimum: 8
+            })
+        );
+        let validated = config.validate();
+        assert_eq!(validated.length, 10);
+        assert_eq!(validated.max_lowercase, Some(8));
+        let password = generate_password(config);
+        assert_eq!(password.len(), 10);
+        let config = PasswordRequirements {
+            length: 12,
+            decimal: 6,
+            specials: 6,
+            first_is_letter: false,
+            ..config
+        };
+        assert_eq!(config.check(), Ok(()));
+        assert!(!generate_password(config).contains(char::is_alphabetic));
+
+        // the length is shortened before the counts are reconciled with it
+        let config = PasswordRequirements {
+            length: 52,
+            specials: 2,
+            max_specials: Some(2),
+            max_uppercase: Some(0),
+            lowercase: 52,
+            ..Default::default()
+        };
+        let validated = config.validate();
+        assert_eq!(validated.length, 29);
+        assert_eq!(validated.specials, 2);
+        assert_eq!(try_generate_password(validated).unwrap().len(), 29);
+    }
+
+    #[test]
+    fn letter_counts() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let sets = CharacterSets::default().with_uppercase(['A', 'B', 'C']);
+        for allow_repeats in [false, true] {
+            for length in [10, 16, 40, 60] {
+                for (decimal, specials) in [(0, 0), (1, 1), (4, 0), (0, 4)] {
+                    for uppercase in [0, 3, 12, 30, 70] {
+                        for lowercase in [0, 2, 9, 30] {
+                            let config = PasswordRequirements {
+                                length,
+                                decimal,
+                                specials,
+                                uppercase,
+                                lowercase,
+                                allow_repeats,
+                                ..Default::default()
+                            };
+                            for sets in [&CharacterSets::default(), &sets] {
+                                let validated = config.validate_with(sets);
+                                assert_eq!(validated.validate_with(sets), validated);
+                                let password = sets.generate_password_with_rng(config, &mut rng);
+                                assert_eq!(password.len(), validated.length as usize);
+                                let (uppers, lowers, decimal, specials, _) = count(&password);
+                                assert!(uppers >= validated.uppercase as usize);
+                                assert!(lowers >= validated.lowercase as usize);
+                                assert_eq!(decimal, validated.decimal as usize);
+                                assert_eq!(specials, validated.specials as usize);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn count_ranges() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let config = PasswordRequirements {
+            decimal: 1,
+            max_decimal: Some(4),
+            specials: 0,
+            max_specials: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(config.check(), Ok(()));
+        let mut seen = HashSet::new();
+        // the least likely combination (4 decimal integers and no special characters)
+        // is picked about 0.5% of the time
+        for _ in 0..2000 {
+            let password = generate_password_with_rng(config, &mut rng);
+            let (uppers, lowers, decimal, specials, _) = count(&password);
+            assert_eq!(password.len(), config.length as usize);
+            assert!((1..=4).contains(&decimal));
+            assert!(specials <= 3);
+            assert!(uppers > 0 && lowers > 0);
+            seen.insert((decimal, specials));
+        }
+        // every combination of counts is picked eventually
+        assert_eq!(seen.len(), 16);
+
+        let config = PasswordRequirements {
+            max_uppercase: Some(3),
+            lowercase: 10,
+            max_lowercase: Some(12),
+            ..Default::default()
+        };
+        for _ in 0..50 {
+            let password = generate_password_with_rng(config, &mut rng);
+            let (uppers, lowers, _, _, _) = count(&password);
+            assert!((2..=3).contains(&uppers));
+            assert!((11..=12).contains(&lowers));
+        }
+
+        let config = PasswordRequirements {
+            decimal: 3,
+            max_decimal: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.check(),
+            Err(RequirementsError::MaximumLessThanMinimum {
+                name: "decimal",
+                minimum: 3,
+                maximum: 2
+            })
+        );
+        assert_eq!(config.validate().max_decimal, Some(3));
+        let config = PasswordRequirements {
+            max_decimal: Some(3),
+            max_specials: Some(3),
+            max_uppercase: Some(2),
+            max_lowercase: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.check(),
+            Err(RequirementsError::TooLong {
+                length: 16,
+                maximum: 10
+            })
+        );
+
+        for (max_decimal, max_specials, max_uppercase) in [
+            (None, Some(5), None),
+            (Some(6), None, Some(8)),
+            (Some(20), Some(20), Some(0)),
+        ] {
+            for allow_repeats in [false, true] {
+                let config = PasswordRequirements {
+                    length: 30,
+                    max_decimal,
+                    max_specials,
+                    max_uppercase,
+                    allow_repeats,
+                    ..Default::default()
+                };
+                let validated = config.validate();
+                assert_eq!(validated.validate(), validated);
+                let sets = CharacterSets::default();
+                let distribution = CountDistribution::new(&sets, validated);
+                for _ in 0..20 {
+                    let counts = distribution.pick(&mut rng);
+                    let length =
+                        counts.uppercase + counts.lowercase + counts.number + counts.special;
+                    assert_eq!(length, validated.length);
+                }
+                let password = generate_password(config);
+                assert_eq!(password.len(), validated.length as usize);
+            }
+        }
+        let exact = PasswordRequirements::default();
+        let ranged = PasswordRequirements {
+            max_decimal: Some(1),
+            max_specials: Some(4),
+            ..Default::default()
+        };
+        assert!(password_entropy(ranged) > password_entropy(exact));
+    }
+
+    #[test]
+    fn long_count_ranges() {
+        let mut rng = StdRng::seed_from_u64(10);
+        for (max_uppercase, max_lowercase) in [(None, None), (Some(u16::MAX), Some(u16::MAX))] {
+            let config = PasswordRequirements {
+                length: u16::MAX,
+                decimal: 0,
+                max_decimal: Some(u16::MAX),
+                specials: 0,
+                max_specials: Some(u16::MAX),
+                max_uppercase,
+                max_lowercase,
+                allow_repeats: true,
+                ..Default::default()
+            };
+            assert_eq!(config.check(), Ok(()));
+            let password = generate_password_with_rng(config, &mut rng);
+            let (uppers, lowers, decimal, specials, _) = count(&password);
+            assert_eq!(password.len(), u16::MAX as usize);
+            // each kind of character is about as common as its share of the 78 characters
+            let expected = |size: usize| u16::MAX as usize * size / 78;
+            assert!(decimal.abs_diff(expected(10)) < 1000);
+            assert!(specials.abs_diff(expected(16)) < 1000);
+            assert!(uppers.abs_diff(expected(26)) < 1000);
+            assert!(lowers.abs_diff(expected(26)) < 1000);
+            let entropy = password_entropy(config);
+            assert!(entropy < u16::MAX as f64 * 78f64.log2());
+            assert!(entropy > u16::MAX as f64 * 77f64.log2());
+        }
+    }
+
+    #[test]
+    fn check_errors() {
+        for (decimal, specials, maximum) in [(1, 1, 54), (10, 16, 78)] {
+            let config = PasswordRequirements {
+                length: 100,
+                decimal,
+                specials,
+                ..Default::default()
+            };
+            assert_eq!(
+                config.check(),
+                Err(RequirementsError::TooLong {
+                    length: 100,
+                    maximum
+                })
+            );
+            // the reported maximum is the final maximum
+            let config = PasswordRequirements {
+                length: maximum,
+                ..config
+            };
+            assert_eq!(config.check(), Ok(()));
+        }
+        let config = PasswordRequirements {
+            length: 70,
+            decimal: 16,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.check(),
+            Err(RequirementsError::TooManyDecimal {
+                decimal: 16,
+                maximum: 10
+            })
+        );
+        let config = PasswordRequirements {
+            length: 60,
+            decimal: 10,
+            specials: 20,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.check(),
+            Err(RequirementsError::TooManySpecials {
+                specials: 20,
+                maximum: 16
+            })
+        );
+        let config = PasswordRequirements {
+            decimal: 15,
+            specials: 0,
+            allow_repeats: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.check(),
+            Err(RequirementsError::DecimalOverrulesOthers {
+                decimal: 15,
+                maximum: 14
+            })
+        );
+        let config = PasswordRequirements {
+            uppercase: 20,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.check(),
+            Err(RequirementsError::UppercaseOverrulesOthers {
+                uppercase: 20,
+                maximum: 13
+            })
+        );
+        let config = PasswordRequirements {
+            length: 40,
+            lowercase: 30,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.check(),
+            Err(RequirementsError::TooManyLowercase {
+                lowercase: 30,
+                maximum: 26
+            })
+        );
+        let sets = CharacterSets::default()
+            .with_uppercase([])
+            .with_lowercase([]);
+        let config = PasswordRequirements::default();
+        assert_eq!(
+            sets.try_generate_password(config),
+            Err(RequirementsError::NoLetters)
+        );
+        let sets =
+            CharacterSets::default().with_lowercase(('a'..='z').chain('\u{30000}'..='\u{3FFFF}'));
+        let size = sets.lowercase().len();
+        assert!(size > u16::MAX as usize);
+        assert_eq!(
+            sets.try_generate_password(config),
+            Err(RequirementsError::SetTooLarge {
+                name: "lowercase",
+                size
+            })
+        );
+        // the oversized set still limits nothing when validating
+        assert_eq!(config.validate_with(&sets), config);
+        let password = try_generate_password(config).unwrap();
+        assert_eq!(password.len(), config.length as usize);
+    }
+
+    #[test]
+    fn entropy() {
+        // 2 uppercase, 2 lowercase, 1 decimal, and 1 special character are all used once
+        let sets = CharacterSets::default()
+            .with_uppercase(['A', 'B'])
+            .with_lowercase(['a', 'b'])
+            .with_decimal(['0'])
+            .with_special(['-']);
+        let config = PasswordRequirements::default();
+        assert_eq!(config.validate_with(&sets).length, 6);
+        // 4 letters can be first, then any arrangement of the other 5 characters
+        assert_eq!(sets.password_entropy(config), (4.0 * 120.0f64).log2());
+        let config = PasswordRequirements {
+            first_is_letter: false,
+            ..Default::default()
+        };
+        assert_eq!(sets.password_entropy(config), 720.0f64.log2());
+
+        let config = PasswordRequirements {
+            allow_repeats: true,
+            ..Default::default()
+        };
+        assert!(password_entropy(config) > password_entropy(PasswordRequirements::default()));
+    }
+
+    #[test]
+    fn seeded_rng() {
+        let sets = CharacterSets::default().without_ambiguous();
+        for config in [
+            PasswordRequirements::default(),
+            PasswordRequirements {
+                allow_repeats: true,
+                length: 64,
+                ..Default::default()
+            },
+        ] {
+            let password = sets.generate_password_with_rng(config, &mut StdRng::seed_from_u64(7));
+            let reproduced = sets.generate_password_with_rng(config, &mut StdRng::seed_from_u64(7));
+            assert_eq!(password, reproduced);
+            let other = sets.generate_password_with_rng(config, &mut StdRng::seed_from_u64(8));
+            assert_ne!(password, other);
+        }
+    }
+
+    #[test]
+    fn batch() {
+        let config = PasswordRequirements::default();
+        let passwords = generate_passwords(config, 20, true);
+        assert_eq!(passwords.len(), 20);
+        for password in &passwords {
+            assert_eq!(password.len(), config.length as usize);
+        }
+        assert_eq!(passwords.iter().collect::<HashSet<_>>().len(), 20);
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let passwords = generate_passwords_with_rng(config, 3, false, &mut rng);
+        let mut rng = StdRng::seed_from_u64(3);
+        for password in passwords {
+            assert_eq!(password, generate_password_with_rng(config, &mut rng));
+        }
+
+        // only 480 passwords are possible with these sets (see the entropy test)
+        let sets = CharacterSets::default()
+            .with_uppercase(['A', 'B'])
+            .with_lowercase(['a', 'b'])
+            .with_decimal(['0'])
+            .with_special(['-']);
+        let passwords = sets.generate_passwords(config, 1000, true);
+        assert_eq!(passwords.len(), 480);
+        assert_eq!(passwords.iter().collect::<HashSet<_>>().len(), 480);
+        assert_eq!(sets.generate_passwords(config, 1000, false).len(), 1000);
+        // nothing is allocated for a huge count up front
+        assert_eq!(sets.generate_passwords(config, usize::MAX, true).len(), 480);
+        let mut passwords = sets.passwords(config, false);
+        assert_eq!(passwords.nth(5000).unwrap().len(), 6);
+
+        // the iterator ends (and stays ended) when the possible passwords are overestimated
+        let mut passwords = sets.passwords(config, true);
+        passwords.possible = 500;
+        assert_eq!(passwords.by_ref().count(), 480);
+        assert!(passwords.next().is_none());
+    }
+
+    #[test]
+    fn analyze() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for config in [
+            PasswordRequirements::default(),
+            PasswordRequirements {
+                length: 30,
+                decimal: 2,
+                max_decimal: Some(5),
+                uppercase: 12,
+                allow_repeats: true,
+                ..Default::default()
+            },
+        ] {
+            let password = generate_password_with_rng(config, &mut rng);
+            let analysis = analyze_password(&password);
+            assert!(analysis.satisfies(config), "{analysis:?}");
+            assert!(analysis.first_is_letter);
+            assert_eq!(analysis.other, 0);
+        }
+
+        let analysis = analyze_password("ñandú-Ok-42-ñ");
+        assert_eq!(analysis.length, 13);
+        assert_eq!(analysis.uppercase, 1);
+        assert_eq!(analysis.lowercase, 4);
+        assert_eq!(analysis.decimal, 2);
+        assert_eq!(analysis.specials, 3);
+        assert_eq!(analysis.other, 3);
+        assert_eq!(analysis.repeated, ['-', 'ñ']);
+        assert!(!analysis.first_is_letter);
+        // 26 + 26 + 10 + 16 + 2 (ñ and ú) possible characters
+        assert_eq!(analysis.entropy, 13.0 * 80.0f64.log2());
+        assert!(!analysis.satisfies(PasswordRequirements::default()));
+
+        let analysis = analyze_password("Abcdefghij0-");
+        let config = PasswordRequirements {
+            length: 12,
+            ..Default::default()
+        };
+        assert!(analysis.satisfies(config));
+        for config in [
+            PasswordRequirements {
+                length: 13,
+                ..config
+            },
+            PasswordRequirements {
+                max_lowercase: Some(8),
+                ..config
+            },
+            PasswordRequirements {
+                uppercase: 2,
+                ..config
+            },
+            PasswordRequirements {
+                specials: 2,
+                ..config
+            },
+        ] {
+            assert!(!analysis.satisfies(config), "{config:?}");
+        }
+
+        // the requirements are not validated (which would change the length)
+        let config = PasswordRequirements {
+            length: 8,
+            ..Default::default()
+        };
+        assert!(analyze_password("Abcdef0-").satisfies(config));
+        let config = PasswordRequirements {
+            length: 100,
+            ..Default::default()
+        };
+        let password = generate_password(config);
+        assert_eq!(password.len(), 54);
+        assert!(!analyze_password(&password).satisfies(config));
+        assert_eq!(analyze_password("").entropy, 0.0);
+    }
+
+    #[test]
+    fn default_wordlist() {
+        let wordlist = Wordlist::default();
+        assert_eq!(wordlist.len(), 7776);
+        assert_eq!(wordlist.words().next(), Some("abacus"));
+        assert_eq!(wordlist.words().last(), Some("zoom"));
+
+        let passphrase = generate_passphrase(&PassphraseRequirements::default(), &wordlist);
+        println!("Generated passphrase: {passphrase}");
+        // the few words in the EFF's list that contain a hyphen (like "t-shirt") are skipped
+        assert_eq!(passphrase.split('-').count(), 6);
+        assert!(wordlist.words().any(|word| word.contains('-')));
+        let mut rng = StdRng::seed_from_u64(6);
+        for _ in 0..500 {
+            let passphrase = generate_passphrase_with_rng(
+                &PassphraseRequirements::default(),
+                &wordlist,
+                &mut rng,
+            );
+            assert_eq!(passphrase.split('-').count(), 6, "{passphrase}");
+        }
+        assert_eq!(passphrase, passphrase.to_lowercase());
+    }
+
+    #[test]
+    fn passphrase_options() {
+        let wordlist = Wordlist::new(["apple", "Banana", "cherry", "apple", ""]);
+        assert_eq!(wordlist.len(), 3);
+        let config = PassphraseRequirements {
+            words: 5,
+            separator: " ".to_string(),
+            capitalize: Capitalization::Title,
+            decimal: 3,
+            specials: 2,
+        };
+        let mut rng = StdRng::seed_from_u64(1);
+        let passphrase = generate_passphrase_with_rng(&config, &wordlist, &mut rng);
+        println!("Generated passphrase: {passphrase}");
+        let words = passphrase.split(' ').collect::<Vec<&str>>();
+        assert_eq!(words.len(), 5);
+        for word in words {
+            let letters = word.trim_end_matches(|ch| !char::is_alphabetic(ch));
+            assert!(["Apple", "Banana", "Cherry"].contains(&letters));
+        }
+        let (_, _, decimal, specials, _) = count(&passphrase);
+        assert_eq!(decimal, 3);
+        assert_eq!(specials, 2);
+
+        let config = PassphraseRequirements {
+            capitalize: Capitalization::Upper,
+            ..Default::default()
+        };
+        let passphrase = generate_passphrase_with_rng(&config, &wordlist, &mut rng);
+        assert_eq!(passphrase, passphrase.to_uppercase());
+
+        // the default separator ('-') is also a special character
+        let config = PassphraseRequirements {
+            words: 4,
+            specials: 40,
+            ..Default::default()
+        };
+        for _ in 0..20 {
+            let passphrase = generate_passphrase_with_rng(&config, &wordlist, &mut rng);
+            assert_eq!(passphrase.split('-').count(), 4, "{passphrase}");
+            let (_, _, _, specials, _) = count(&passphrase);
+            assert_eq!(specials, 40 + 3);
+        }
+
+        // words that contain the separator are only used if there are no others
+        let wordlist = Wordlist::new(["t-shirt", "shirt"]);
+        let config = PassphraseRequirements::default();
+        let passphrase = generate_passphrase_with_rng(&config, &wordlist, &mut rng);
+        assert_eq!(passphrase, ["shirt"; 6].join("-"));
+        let wordlist = Wordlist::new(["t-shirt"]);
+        let passphrase = generate_passphrase_with_rng(&config, &wordlist, &mut rng);
+        assert_eq!(passphrase, ["t-shirt"; 6].join("-"));
+    }
+
+    #[cfg(feature = "policy")]
+    #[test]
+    fn policy_formats() {
+        use crate::{Policy, PolicyError, PolicyFormat};
+
+        let toml =
+            "[default]\nlength = 20\n\n[pin-like]\nlength = 12\ndecimal = 4\nmaxDecimal = 6\n";
+        let json = r#"{"default": {"length": 20}, "pin-like": {"length": 12, "decimal": 4, "maxDecimal": 6}}"#;
+        let yaml =
+            "default:\n  length: 20\npin-like:\n  length: 12\n  decimal: 4\n  maxDecimal: 6\n";
+        let expected = PasswordRequirements {
+            length: 12,
+            decimal: 4,
+            max_decimal: Some(6),
+            ..Default::default()
+        };
+        for (text, format) in [
+            (toml, PolicyFormat::Toml),
+            (json, PolicyFormat::Json),
+            (yaml, PolicyFormat::Yaml),
+        ] {
+            let policy = Policy::parse(text, format).unwrap();
+            assert_eq!(
+                policy.profiles().collect::<Vec<&str>>(),
+                ["default", "pin-like"]
+            );
+            assert_eq!(policy.profile(Some("pin-like")).unwrap(), expected);
+            assert_eq!(policy.profile(None).unwrap().length, 20);
+        }
+
+        assert_eq!(
+            PolicyFormat::from_path("policy.YML"),
+            Some(PolicyFormat::Yaml)
+        );
+        assert_eq!(PolicyFormat::from_path("policy.txt"), None);
+        assert!(matches!(
+            Policy::parse("[default]\nlenght = 20", PolicyFormat::Toml),
+            Err(PolicyError::Parse { .. })
+        ));
+        assert!(matches!(
+            Policy::from_file("policy.txt"),
+            Err(PolicyError::UnknownFormat { .. })
+        ));
+        assert!(matches!(
+            Policy::from_file("does-not-exist.toml"),
+            Err(PolicyError::Read { .. })
+        ));
+
+        let mut policy = Policy::default();
+        policy.insert("only", expected);
+        assert_eq!(policy.profile(None).unwrap(), expected);
+        policy.insert("other", PasswordRequirements::default());
+        assert_eq!(
+            policy.profile(None),
+            Err(PolicyError::NoDefaultProfile {
+                available: vec!["only".to_string(), "other".to_string()]
+            })
+        );
+        assert_eq!(
+            policy.profile(Some("missing")),
+            Err(PolicyError::UnknownProfile {
+                name: "missing".to_string(),
+                available: vec!["only".to_string(), "other".to_string()]
+            })
+        );
+    }
+
+    #[cfg(all(feature = "clap", feature = "policy"))]
+    #[test]
+    fn cli_policy() {
+        use crate::{Cli, Command};
+
+        let path = std::env::temp_dir().join(format!("mk-pass-policy-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[database]\nlength = 32\nspecials = 0\nallowRepeats = true\n",
+        )
+        .unwrap();
+        let path_arg = path.to_str().unwrap();
+
+        let cli = Cli::parse_args(["mk-pass", "--policy", path_arg, "-l", "40", "-s", "1"]);
+        let expected = PasswordRequirements {
+            length: 40,
+            specials: 1,
+            allow_repeats: true,
+            ..Default::default()
+        };
+        assert_eq!(cli.requirements, expected);
+
+        let cli = Cli::parse_args(["mk-pass", "check", "-p", path_arg, "-P", "database", "pw"]);
+        let Some(Command::Check(args)) = cli.command else {
+            panic!("expected the check subcommand");
+        };
+        assert_eq!(args.requirements.length, 32);
+        assert_eq!(args.requirements.specials, 0);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "policy")]
+    #[test]
+    fn serde_round_trip() {
+        let config = PasswordRequirements {
+            length: 20,
+            decimal: 2,
+            max_decimal: Some(4),
+            uppercase: 3,
+            max_lowercase: Some(12),
+            first_is_letter: false,
+            allow_repeats: true,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            r#"{"length":20,"decimal":2,"maxDecimal":4,"specials":1,"uppercase":3,"lowercase":0,"maxLowercase":12,"firstIsLetter":false,"allowRepeats":true}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<PasswordRequirements>(&json).unwrap(),
+            config
+        );
+        let text = toml::to_string(&config).unwrap();
+        assert_eq!(
+            toml::from_str::<PasswordRequirements>(&text).unwrap(),
+            config
+        );
+        let yaml = serde_yaml_ng::to_string(&config).unwrap();
+        assert_eq!(
+            serde_yaml_ng::from_str::<PasswordRequirements>(&yaml).unwrap(),
+            config
+        );
+
+        let default = serde_json::to_string(&PasswordRequirements::default()).unwrap();
+        assert!(!default.contains("max"));
+        assert_eq!(
+            serde_json::from_str::<PasswordRequirements>("{}").unwrap(),
+            PasswordRequirements::default()
+        );
+        assert!(
+            serde_json::from_str::<PasswordRequirements>(r#"{"first_is_letter":false}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn presets() {
+        use crate::Preset;
+
+        let mut rng = StdRng::seed_from_u64(3);
+        for preset in Preset::ALL {
+            let config = PasswordRequirements::preset(preset);
+            assert!(config.check().is_ok(), "{preset}");
+            assert_eq!(preset.name().parse::<Preset>(), Ok(preset));
+            for _ in 0..20 {
+                let password = generate_password_with_rng(config, &mut rng);
+                let analysis = analyze_password(&password);
+                assert_eq!(analysis.length, config.length as usize);
+                assert!(analysis.satisfies(config), "{preset}: {password}");
+            }
+
+            // the counts of decimal integers and special characters are unbounded,
+            // which must not slow down the generation of long passwords
+            let config = PasswordRequirements {
+                length: 16384,
+                ..config
+            };
+            let password = generate_password_with_rng(config, &mut rng);
+            let analysis = analyze_password(&password);
+            assert_eq!(analysis.length, config.length as usize);
+            assert!(analysis.satisfies(config), "{preset}");
+        }
+        assert_eq!(PasswordRequirements::preset(Preset::Nist80063b).length, 15);
+        assert_eq!(PasswordRequirements::preset(Preset::PciDss).decimal, 1);
+        assert!("nist".parse::<Preset>().is_err());
+    }
+
+    #[cfg(all(feature = "clap", feature = "policy"))]
+    #[test]
+    fn cli_preset() {
+        use crate::{Cli, Preset};
+        use clap::CommandFactory;
+
+        let cli = Cli::parse_args(["mk-pass", "--preset", "cis", "-s", "2"]);
+        let expected = PasswordRequirements {
+            specials: 2,
+            ..PasswordRequirements::preset(Preset::Cis)
+        };
+        assert_eq!(cli.requirements, expected);
+        assert!(
+            Cli::command()
+                .try_get_matches_from(["mk-pass", "-t", "cis", "-p", "x.toml"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn password_rules() {
+        use crate::{PasswordRules, PasswordRulesError};
+
+        let rules = PasswordRules::parse(
+            "minlength: 12; maxlength: 30; required: upper, lower; required: digit; \
+            required: [];-]; max-consecutive: 1",
+        )
+        .unwrap();
+        assert_eq!(rules.requirements.length, 12);
+        assert_eq!(rules.requirements.decimal, 1);
+        assert_eq!(rules.requirements.specials, 1);
+        assert!(!rules.requirements.allow_repeats);
+        assert_eq!(rules.sets.special(), [']', ';', '-']);
+        assert_eq!(rules.sets.uppercase(), UPPERCASE);
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..20 {
+            let password = rules
+                .sets
+                .generate_password_with_rng(rules.requirements, &mut rng);
+            let analysis = rules.sets.analyze_password(&password);
+            assert_eq!(analysis.length, 12);
+            assert_eq!(analysis.other, 0);
+            assert!(
+                analysis.decimal >= 1 && analysis.specials >= 1,
+                "{password}"
+            );
+            assert!(analysis.satisfies(rules.requirements));
+        }
+
+        // serializing and parsing again does not change the rules
+        let text = rules.to_string();
+        assert_eq!(
+            text,
+            "minlength: 12; maxlength: 30; required: upper, lower; required: digit; \
+            required: [];-]; max-consecutive: 1"
+        );
+        assert_eq!(text.parse::<PasswordRules>().unwrap(), rules);
+        for text in [
+            "minlength: 12; maxlength: 30",
+            "maxlength: 20; allowed: lower, upper",
+            "minlength: 20; required: lower; allowed: digit; max-consecutive: 3",
+            "required: upper; required: [-_]; allowed: lower",
+        ] {
+            let rules = PasswordRules::parse(text).unwrap();
+            let displayed = rules.to_string();
+            assert_eq!(
+                PasswordRules::parse(&displayed),
+                Ok(rules.clone()),
+                "{text}"
+            );
+            assert_eq!(rules.to_string(), displayed, "{text}");
+        }
+
+        // any limit of consecutive characters disables repeats
+        let rules = PasswordRules::parse("max-consecutive: 2").unwrap();
+        assert!(!rules.requirements.allow_repeats);
+        assert!(rules.to_string().ends_with("; max-consecutive: 2"));
+
+        // a required class that only covers some of a kind is kept with the allowed kind
+        let text = "minlength: 12; required: upper, [abc]; allowed: lower";
+        let rules = PasswordRules::parse(text).unwrap();
+        assert_eq!(rules.to_string(), text);
+        assert_eq!(text.parse::<PasswordRules>().unwrap(), rules);
+
+        // no classes allow all ASCII printable characters
+        let rules = PasswordRules::parse("maxlength: 12").unwrap();
+        assert_eq!(rules.requirements.length, 12);
+        assert_eq!(rules.requirements.decimal, 0);
+        assert_eq!(rules.requirements.max_decimal, Some(u16::MAX));
+        assert_eq!(rules.sets.special().len(), 33);
+        assert_eq!(
+            rules.to_string(),
+            "minlength: 12; maxlength: 12; allowed: upper, lower, digit, special"
+        );
+
+        // the length is clamped into the minimum and maximum lengths
+        for (text, length) in [
+            ("minlength: 6", 10),
+            ("minlength: 6; maxlength: 12", 10),
+            ("minlength: 20; maxlength: 30", 20),
+            ("maxlength: 30", 16),
+        ] {
+            let rules = PasswordRules::parse(text).unwrap();
+            assert_eq!(rules.requirements.length, length, "{text}");
+        }
+
+        let rules = PasswordRules::new(
+            PasswordRequirements::default(),
+            CharacterSets::default().with_special(['-', '_']),
+        );
+        assert_eq!(
+            rules.to_string(),
+            "minlength: 16; required: upper; required: lower; required: digit; \
+            required: [-_]; max-consecutive: 1"
+        );
+
+        for (text, err) in [
+            (
+                "required: unicode",
+                PasswordRulesError::UnknownClass {
+                    name: "unicode".to_string(),
+                },
+            ),
+            (
+                "minlength 20",
+                PasswordRulesError::MissingValue {
+                    clause: "minlength 20".to_string(),
+                },
+            ),
+            (
+                "max-length: 20",
+                PasswordRulesError::UnknownProperty {
+                    name: "max-length".to_string(),
+                },
+            ),
+            (
+                "minlength: -1",
+                PasswordRulesError::InvalidNumber {
+                    property: "minlength".to_string(),
+                    value: "-1".to_string(),
+                },
+            ),
+            (
+                "minlength: 20; maxlength: 12",
+                PasswordRulesError::InvalidLength {
+                    minimum: 20,
+                    maximum: 12,
+                },
+            ),
+            (
+                "minlength: 6; maxlength: 8",
+                PasswordRulesError::InvalidLength {
+                    minimum: 10,
+                    maximum: 8,
+                },
+            ),
+            (
+                "max-consecutive: 0",
+                PasswordRulesError::InvalidNumber {
+                    property: "max-consecutive".to_string(),
+                    value: "0".to_string(),
+                },
+            ),
+            (
+                "minlength: 40; allowed: lower; max-consecutive: 1",
+                PasswordRulesError::UnreachableLength {
+                    minimum: 40,
+                    maximum: 26,
+                },
+            ),
+            (
+                "required: [abc",
+                PasswordRulesError::UnterminatedClass {
+                    class: "[abc".to_string(),
+                },
+            ),
+            (
+                "allowed: [ñ]",
+                PasswordRulesError::UnsupportedCharacter { ch: 'ñ' },
+            ),
+            ("allowed: digit, special", PasswordRulesError::NoLetters),
+            (
+                "required: [abc]; allowed: lower, digit",
+                PasswordRulesError::UnsupportedRequirement {
+                    class: "[abc]".to_string(),
+                },
+            ),
+        ] {
+            assert_eq!(PasswordRules::parse(text), Err(err), "{text}");
+        }
+    }
+
+    #[test]
+    fn unicode_alphabets() {
+        use crate::Alphabet;
+        use unicode_normalization::UnicodeNormalization;
+
+        let sizes = [
+            (Alphabet::Latin1, 30, 32),
+            (Alphabet::Greek, 24, 25),
+            (Alphabet::Cyrillic, 33, 33),
+        ];
+        for (alphabet, upper, lower) in sizes {
+            assert_eq!(alphabet.uppercase().len(), upper, "{alphabet}");
+            assert_eq!(alphabet.lowercase().len(), lower, "{alphabet}");
+            assert_eq!(alphabet.name().parse::<Alphabet>(), Ok(alphabet));
+        }
+
+        let sets = Alphabet::ALL
+            .into_iter()
+            .fold(CharacterSets::default(), CharacterSets::with_alphabet);
+        assert_eq!(sets.uppercase().len(), UPPERCASE.len() + 30 + 24 + 33);
+        let config = PasswordRequirements {
+            length: 100,
+            ..Default::default()
+        };
+        let validated = config.validate_with(&sets);
+        assert_eq!(validated.length, 100);
+        let mut rng = StdRng::seed_from_u64(5);
+        let password = sets.generate_password_with_rng(config, &mut rng);
+        println!("Generated password: {password}");
+        assert_eq!(password.chars().count(), 100);
+        assert!(password.len() > 100);
+        assert_eq!(password.nfc().collect::<String>(), password);
+        let analysis = sets.analyze_password(&password);
+        assert_eq!(analysis.length, 100);
+        assert_eq!(analysis.other, 0);
+        assert!(analysis.satisfies(config));
+
+        // sets are normalized to NFC without combining marks
+        let sets = CharacterSets::default().with_special(['\u{212b}', '\u{301}', 'Å', '€']);
+        assert_eq!(sets.special(), ['Å', '€']);
+        // so are excluded characters
+        let sets = sets.exclude(['\u{212b}']);
+        assert_eq!(sets.special(), ['€']);
+        let sets = CharacterSets::default()
+            .with_alphabet(Alphabet::Greek)
+            .exclude(['\u{2126}']);
+        assert!(!sets.uppercase().contains(&'\u{3a9}'));
+    }
+
+    #[test]
+    fn pronounceable() {
+        for config in [
+            PasswordRequirements::default(),
+            PasswordRequirements {
+                length: 24,
+                decimal: 3,
+                specials: 2,
+                uppercase: 2,
+                ..Default::default()
+            },
+            PasswordRequirements {
+                specials: 4,
+                first_is_letter: false,
+                ..Default::default()
+            },
+        ] {
+            for _ in 0..50 {
+                let password = generate_pronounceable_password(config);
+                println!("Generated password: {password}");
+                assert_eq!(password.len(), config.length as usize);
+                let (uppers, _, decimal, specials, _) = count(&password);
+                assert_eq!(decimal, config.decimal as usize);
+                assert_eq!(specials, config.specials as usize);
+                assert!(uppers >= config.uppercase.max(1) as usize);
+                if config.first_is_letter {
+                    assert!(password.starts_with(|ch: char| ch.is_ascii_alphabetic()));
+                }
+
+                // consonant and vowel clusters have at most 2 letters
+                let letters = password
+                    .chars()
+                    .filter(char::is_ascii_alphabetic)
+                    .map(|ch| "aeiou".contains(ch.to_ascii_lowercase()))
+                    .collect::<Vec<_>>();
+                assert!(letters.windows(3).all(|w| w[0] != w[1] || w[1] != w[2]));
+            }
+
+            let bits = pronounceable_entropy(config);
+            assert!(bits > 0.0);
+            assert!(bits < password_entropy(config));
+        }
+
+        let config = PasswordRequirements::default();
+        let sets = CharacterSets::default().without_ambiguous();
+        for _ in 0..50 {
+            let password = sets.generate_pronounceable_password(config);
+            assert!(!password.contains(AMBIGUOUS_CHARACTERS));
+        }
+
+        // excluded letters are never used, even if that leaves a single vowel
+        let sets = CharacterSets::default().exclude("aeiouAEIO".chars());
+        assert_eq!(
+            sets.try_generate_pronounceable_password(config),
+            Err(RequirementsError::NotPronounceable)
+        );
+        assert_eq!(sets.pronounceable_entropy(config), 0.0);
+        let sets = CharacterSets::default().exclude("aeioAEIO".chars());
+        for _ in 0..50 {
+            let password = sets.try_generate_pronounceable_password(config).unwrap();
+            assert!(
+                !password.contains(|ch| "aeioAEIO".contains(ch)),
+                "{password}"
+            );
+        }
+
+        let password =
+            generate_pronounceable_password_with_rng(config, &mut StdRng::seed_from_u64(7));
+        let reproduced =
+            generate_pronounceable_password_with_rng(config, &mut StdRng::seed_from_u64(7));
+        assert_eq!(password, reproduced);
+    }
+
+    #[test]
+    fn pins() {
+        use crate::{PinPattern, PinRequirements, generate_pin, generate_pin_with_rng};
+        use crate::{pin::COMMON_PINS, weak_pin_pattern};
+
+        for (pin, pattern) in [
+            ("0000", PinPattern::Repeated),
+            ("1212", PinPattern::Repeated),
+            ("123123", PinPattern::Repeated),
+            ("1234", PinPattern::Sequence),
+            ("9876", PinPattern::Sequence),
+            ("7890", PinPattern::Sequence),
+            ("0229", PinPattern::Date),
+            ("3112", PinPattern::Date),
+            ("1984", PinPattern::Date),
+            ("250599", PinPattern::Date),
+            ("991231", PinPattern::Date),
+            ("07041976", PinPattern::Date),
+            ("20240131", PinPattern::Date),
+        ] {
+            assert_eq!(weak_pin_pattern(pin), Some(pattern), "{pin}");
+        }
+        for pin in COMMON_PINS {
+            assert_eq!(weak_pin_pattern(pin), Some(PinPattern::Common), "{pin}");
+        }
+        for pin in [
+            "8375", "0230", "3212", "13579", "482915", "31131999", "12a4", "",
+        ] {
+            assert_eq!(weak_pin_pattern(pin), None, "{pin}");
+        }
+
+        for config in [
+            PinRequirements::default(),
+            PinRequirements {
+                length: 4,
+                allow_repeats: true,
+            },
+            PinRequirements {
+                length: 20,
+                allow_repeats: false,
+            },
+            PinRequirements {
+                length: 2,
+                allow_repeats: false,
+            },
+        ] {
+            let validated = config.validate();
+            for _ in 0..100 {
+                let pin = generate_pin(config);
+                assert_eq!(pin.len(), validated.length as usize);
+                assert!(pin.chars().all(|ch| DECIMAL.contains(&ch)));
+                assert_eq!(weak_pin_pattern(&pin), None, "{pin}");
+                if !config.allow_repeats {
+                    assert_eq!(pin.chars().collect::<HashSet<_>>().len(), pin.len());
+                }
+            }
+        }
+        assert_eq!(PinRequirements::default().validate().length, 6);
+
+        let config = PinRequirements::default();
+        let pin = generate_pin_with_rng(config, &mut StdRng::seed_from_u64(7));
+        assert_eq!(
+            pin,
+            generate_pin_with_rng(config, &mut StdRng::seed_from_u64(7))
+        );
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn cli_pin() {
+        use crate::{Cli, Command};
+
+        let cli = Cli::parse_args(["mk-pass", "pin", "-l", "8", "-r", "-n", "3"]);
+        let Some(Command::Pin(args)) = cli.command else {
+            panic!("expected the pin subcommand");
+        };
+        assert_eq!(args.requirements.length, 8);
+        assert!(args.requirements.allow_repeats);
+        assert_eq!(args.count, 3);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn secret_password() {
+        use crate::{SecretString, generate_secret_password, generate_secret_password_with_rng};
+        use zeroize::Zeroize;
+
+        let config = PasswordRequirements::default();
+        let password = generate_secret_password(config);
+        assert_eq!(password.expose_secret().len(), config.length as usize);
+        assert!(!format!("{password:?}").contains(password.expose_secret()));
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let secret = generate_secret_password_with_rng(config, &mut rng);
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(
+            secret.expose_secret(),
+            generate_password_with_rng(config, &mut rng)
+        );
+
+        let mut secret = SecretString::from("hunter2".to_string());
+        secret.zeroize();
+        assert!(secret.expose_secret().is_empty());
+    }
+
+    /// Assert that the `observed` frequencies fit the `expected` frequencies
+    /// according to Pearson's chi-square test (at a significance level of about 0.0001).
+    fn assert_chi_square(name: &str, observed: &[usize], expected: &[f64]) {
+        assert_eq!(observed.len(), expected.len());
+        let chi_square = observed
+            .iter()
+            .zip(expected)
+            .map(|(&o, &e)| (o as f64 - e).powi(2) / e)
+            .sum::<f64>();
+        // the Wilson-Hilferty approximation of the critical value
+        let df = (observed.len() - 1) as f64;
+        let a = 2.0 / (9.0 * df);
+        let critical = df * (1.0 - a + 3.72 * a.sqrt()).powi(3);
+        println!("{name}: chi-square {chi_square:.2} (critical {critical:.2}, df {df})");
+        assert!(chi_square < critical, "{name} is not uniformly distributed");
+    }
+
+    /// Assert that every possible password is generated equally often.
+    fn assert_uniform_passwords(sets: &CharacterSets, config: PasswordRequirements, seed: u64) {
+        const SAMPLES_PER_PASSWORD: usize = 20;
+        let total = sets.password_entropy(config).exp2().round() as usize;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut frequencies = std::collections::HashMap::new();
+        for _ in 0..total * SAMPLES_PER_PASSWORD {
+            let password = sets.generate_password_with_rng(config, &mut rng);
+            *frequencies.entry(password).or_insert(0) += 1;
+        }
+        assert_eq!(frequencies.len(), total);
+        let observed = frequencies.into_values().collect::<Vec<usize>>();
+        let expected = vec![SAMPLES_PER_PASSWORD as f64; total];
+        assert_chi_square("passwords", &observed, &expected);
+    }
+
+    #[test]
+    fn uniform_passwords() {
+        // 480 possible passwords without repetitions (see the entropy test)
+        let sets = CharacterSets::default()
+            .with_uppercase(['A', 'B'])
+            .with_lowercase(['a', 'b'])
+            .with_decimal(['0'])
+            .with_special(['-']);
+        assert_uniform_passwords(&sets, PasswordRequirements::default(), 1);
+
+        // 17514 possible passwords with ranges of counts
+        let sets = CharacterSets::default()
+            .with_uppercase(['A'])
+            .with_lowercase(['a'])
+            .with_decimal(['0'])
+            .with_special(['-']);
+        let config = PasswordRequirements {
+            length: 10,
+            max_decimal: Some(2),
+            specials: 0,
+            max_specials: Some(1),
+            allow_repeats: true,
+            ..Default::default()
+        };
+        assert_eq!(sets.password_entropy(config).exp2().round(), 17514.0);
+        assert_uniform_passwords(&sets, config, 2);
+    }
+
+    #[test]
+    fn uniform_characters() {
+        const SAMPLES: usize = 4000;
+        let sets = CharacterSets::default();
+        for allow_repeats in [false, true] {
+            let config = PasswordRequirements {
+                length: 20,
+                decimal: 3,
+                specials: 3,
+                allow_repeats,
+                ..Default::default()
+            };
+            let mut rng = StdRng::seed_from_u64(3);
+            let mut frequencies = std::collections::HashMap::new();
+            for _ in 0..SAMPLES {
+                for ch in sets.generate_password_with_rng(config, &mut rng).chars() {
+                    *frequencies.entry(ch).or_insert(0) += 1;
+                }
+            }
+            for (name, set, count) in [
+                ("uppercase", sets.uppercase(), 7),
+                ("lowercase", sets.lowercase(), 7),
+                ("decimal", sets.decimal(), 3),
+                ("special", sets.special(), 3),
+            ] {
+                let observed = set
+                    .iter()
+                    .map(|ch| frequencies.get(ch).copied().unwrap_or_default())
+                    .collect::<Vec<usize>>();
+                let expected = (SAMPLES * count) as f64 / set.len() as f64;
+                assert_chi_square(name, &observed, &vec![expected; set.len()]);
+            }
+        }
+    }
+
+    #[test]
+    fn uniform_positions() {
+        const SAMPLES: usize = 4000;
+        let sets = CharacterSets::default();
+        for first_is_letter in [true, false] {
+            let config = PasswordRequirements {
+                first_is_letter,
+                ..Default::default()
+            };
+            // 7 uppercase, 7 lowercase, 1 decimal, and 1 special character
+            let len = config.length as usize;
+            let mut frequencies = vec![[0; 4]; len];
+            let mut rng = StdRng::seed_from_u64(4);
+            for _ in 0..SAMPLES {
+                let password = sets.generate_password_with_rng(config, &mut rng);
+                for (position, ch) in password.chars().enumerate() {
+                    let kind = [UPPERCASE, LOWERCASE]
+                        .iter()
+                        .position(|set| set.contains(&ch))
+                        .unwrap_or(if DECIMAL.contains(&ch) { 2 } else { 3 });
+                    frequencies[position][kind] += 1;
+                }
+            }
+            for (position, observed) in frequencies.iter().enumerate() {
+                let probabilities = match (first_is_letter, position) {
+                    (true, 0) => vec![0.5, 0.5],
+                    (true, _) => vec![6.5 / 15.0, 6.5 / 15.0, 1.0 / 15.0, 1.0 / 15.0],
+                    (false, _) => vec![7.0 / 16.0, 7.0 / 16.0, 1.0 / 16.0, 1.0 / 16.0],
+                };
+                let expected = probabilities
+                    .iter()
+                    .map(|p| p * SAMPLES as f64)
+                    .collect::<Vec<f64>>();
+                let name = format!("position {position}");
+                assert_chi_square(&name, &observed[..expected.len()], &expected);
+            }
+        }
+    }
 }
@@ -1,18 +1,16 @@
 #[cfg(not(test))]
 use std::env;
 
-use clap::Parser;
-use mk_pass::{PasswordRequirements, generate_password};
+use mk_pass::Cli;
 
 fn main() {
-    let config = PasswordRequirements::parse_from(
+    let cli = Cli::parse_args(
         #[cfg(test)]
         vec!["mk-pass"],
         #[cfg(not(test))]
         env::args(),
     );
-    let password = generate_password(config);
-    println!("{password}");
+    cli.run();
 }
 
 #[cfg(test)]
@@ -0,0 +1,267 @@
+use std::{borrow::Cow, collections::HashSet, path::Path};
+
+use rand::prelude::*;
+
+use crate::helpers::{DECIMAL, SPECIAL_CHARACTERS};
+
+#[cfg(feature = "clap")]
+use clap::{Args, ValueEnum};
+
+/// The default list of words (one per line) preceded by their dice rolls.
+///
+/// This is the [EFF's large wordlist](https://www.eff.org/dice) (licensed under
+/// [CC BY 3.0 US](https://creativecommons.org/licenses/by/3.0/us/)).
+const EFF_LARGE_WORDLIST: &str = include_str!("eff_large_wordlist.txt");
+
+/// A list of words from which a passphrase is generated.
+///
+/// By default, this is the [EFF's large wordlist](https://www.eff.org/dice)
+/// which contains 7776 words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wordlist {
+    words: Vec<Cow<'static, str>>,
+}
+
+impl Default for Wordlist {
+    /// Create the default wordlist.
+    fn default() -> Self {
+        Self {
+            words: EFF_LARGE_WORDLIST
+                .lines()
+                .filter_map(Self::parse_line)
+                .map(Cow::Borrowed)
+                .collect(),
+        }
+    }
+}
+
+impl Wordlist {
+    /// Create a wordlist from the given `words`.
+    ///
+    /// Empty words and duplicate words are ignored.
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = S>) -> Self {
+        let mut seen = HashSet::new();
+        let mut list: Vec<Cow<'static, str>> = vec![];
+        for word in words {
+            let word = word.into();
+            if !word.is_empty() && seen.insert(word.clone()) {
+                list.push(Cow::Owned(word));
+            }
+        }
+        Self { words: list }
+    }
+
+    /// Parse a wordlist from the given `text`.
+    ///
+    /// Each line is expected to contain a word. Optionally, a word can be preceded by
+    /// its dice rolls and whitespace (as in a diceware wordlist).
+    /// Blank lines and lines that start with `#` are ignored.
+    ///
+    /// ```rust
+    /// use mk_pass::Wordlist;
+    /// let list = Wordlist::parse("# comment\n11111\tabacus\n11112\tabdomen\nzoom\n");
+    /// assert_eq!(list.words().collect::<Vec<&str>>(), ["abacus", "abdomen", "zoom"]);
+    /// ```
+    pub fn parse(text: &str) -> Self {
+        Self::new(text.lines().filter_map(Self::parse_line))
+    }
+
+    /// Read and [parse](Wordlist::parse()) a wordlist from the file at the given `path`.
+    ///
+    /// An error is returned if the file could not be read or if it does not contain any words.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Failed to read {}: {e}", path.display()))
+        })?;
+        let list = Self::parse(&text);
+        if list.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("No words found in {}", path.display()),
+            ));
+        }
+        Ok(list)
+    }
+
+    /// Get the word from a `line` of text (if any).
+    fn parse_line(line: &str) -> Option<&str> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        match line.split_once(char::is_whitespace) {
+            Some((rolls, word)) if rolls.chars().all(|ch| ch.is_ascii_digit()) => {
+                Some(word.trim_start())
+            }
+            _ => Some(line),
+        }
+    }
+
+    /// The words in this list.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(|w| w.as_ref())
+    }
+
+    /// The number of words in this list.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Is this list empty?
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// The style of capitalization applied to each word in a passphrase.
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Capitalization {
+    /// All letters are lowercase.
+    #[default]
+    Lower,
+
+    /// The first letter of each word is uppercase.
+    Title,
+
+    /// All letters are uppercase.
+    Upper,
+
+    /// The first letter of each word is randomly uppercase or lowercase.
+    Random,
+}
+
+/// A structure to describe passphrase requirements.
+#[cfg_attr(feature = "clap", derive(Args))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassphraseRequirements {
+    /// How many words should the passphrase contain?
+    #[cfg_attr(feature = "clap", arg(long, short, default_value = "6"))]
+    pub words: u16,
+
+    /// The text used to separate words.
+    #[cfg_attr(feature = "clap", arg(long, short = 'S', default_value = "-"))]
+    pub separator: String,
+
+    /// How should the words be capitalized?
+    #[cfg_attr(
+        feature = "clap",
+        arg(long, short, value_enum, default_value_t = Capitalization::Lower)
+    )]
+    pub capitalize: Capitalization,
+
+    /// How many decimal integer characters should be injected into the passphrase?
+    #[cfg_attr(feature = "clap", arg(long, short, default_value = "0"))]
+    pub decimal: u16,
+
+    /// How many special characters should be injected into the passphrase?
+    #[cfg_attr(feature = "clap", arg(long, short, default_value = "0"))]
+    pub specials: u16,
+}
+
+impl Default for PassphraseRequirements {
+    /// Create default passphrase requirements.
+    fn default() -> Self {
+        Self {
+            words: 6,
+            separator: "-".to_string(),
+            capitalize: Capitalization::Lower,
+            decimal: 0,
+            specials: 0,
+        }
+    }
+}
+
+/// Generate a passphrase from the given `wordlist` as specified by `config`.
+///
+/// Words are picked uniformly (and may be repeated) from the `wordlist`.
+/// Any [`DECIMAL`] or [`SPECIAL_CHARACTERS`] required by the `config` are
+/// appended to randomly chosen words. At least 1 word is always used.
+///
+/// Words that contain the [`PassphraseRequirements::separator`] (like "t-shirt" in the
+/// default wordlist) are skipped, and the characters of the separator are never injected,
+/// so that the passphrase can be split into its words again.
+/// If every word contains the separator, then the words are picked from the entire `wordlist`.
+/// If the separator contains all the characters of a kind, then none of that kind are injected.
+///
+/// ```rust
+/// use mk_pass::{PassphraseRequirements, Wordlist, generate_passphrase};
+/// let config = PassphraseRequirements {
+///     words: 4,
+///     separator: " ".to_string(),
+///     ..Default::default()
+/// };
+/// let passphrase = generate_passphrase(&config, &Wordlist::default());
+/// assert_eq!(passphrase.split(' ').count(), 4);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the `wordlist` is empty.
+pub fn generate_passphrase(config: &PassphraseRequirements, wordlist: &Wordlist) -> String {
+    generate_passphrase_with_rng(config, wordlist, &mut rand::rng())
+}
+
+/// Generate a passphrase from the given `wordlist` as specified by `config` using the given `rng`.
+///
+/// See also [`generate_passphrase()`].
+///
+/// # Panics
+///
+/// Panics if the `wordlist` is empty.
+pub fn generate_passphrase_with_rng<R: CryptoRng + Rng>(
+    config: &PassphraseRequirements,
+    wordlist: &Wordlist,
+    rng: &mut R,
+) -> String {
+    assert!(!wordlist.is_empty(), "The wordlist cannot be empty");
+    // never pick a word that contains the separator, so the words can be split apart again
+    let mut candidates = wordlist
+        .words()
+        .filter(|word| config.separator.is_empty() || !word.contains(config.separator.as_str()))
+        .collect::<Vec<&str>>();
+    if candidates.is_empty() {
+        candidates = wordlist.words().collect();
+    }
+    let mut words = (0..config.words.max(1))
+        .map(|_| {
+            let word = candidates[rng.random_range(0..candidates.len())];
+            let title = match config.capitalize {
+                Capitalization::Lower => false,
+                Capitalization::Upper => return word.to_uppercase(),
+                Capitalization::Title => true,
+                Capitalization::Random => rng.random_bool(0.5),
+            };
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if title => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                _ => word.to_lowercase(),
+            }
+        })
+        .collect::<Vec<String>>();
+
+    // never inject a character of the separator, so the words can be split apart again
+    let without_separator = |set: &[char]| {
+        set.iter()
+            .copied()
+            .filter(|ch| !config.separator.contains(*ch))
+            .collect::<Vec<char>>()
+    };
+    for (count, sample) in [
+        (config.decimal, without_separator(&DECIMAL)),
+        (config.specials, without_separator(&SPECIAL_CHARACTERS)),
+    ] {
+        if sample.is_empty() {
+            continue;
+        }
+        for _ in 0..count {
+            let index = rng.random_range(0..words.len());
+            words[index].push(sample[rng.random_range(0..sample.len())]);
+        }
+    }
+    words.join(&config.separator)
+}
@@ -0,0 +1,215 @@
+use std::fmt::Display;
+
+use rand::{prelude::*, seq::index::sample};
+
+use crate::helpers::DECIMAL;
+
+#[cfg(feature = "clap")]
+use clap::{ArgAction, Args};
+
+/// The minimum length of a PIN.
+const MIN_PIN_LENGTH: u16 = 4;
+
+/// Frequently used PINs (like keypad patterns) that are not covered by the other weak patterns.
+pub(crate) const COMMON_PINS: [&str; 16] = [
+    "0258", "0852", "1357", "1470", "2468", "2580", "3690", "8520", "123321", "147258", "159357",
+    "159753", "258456", "369258", "654789", "789456",
+];
+
+/// A weak pattern that is rejected when generating a PIN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinPattern {
+    /// The PIN is a repeated block of digits (like `1111` or `1212`).
+    Repeated,
+
+    /// Each digit is 1 more (or 1 less) than the previous digit (like `1234` or `9876`).
+    ///
+    /// The digits may wrap around (like `7890`).
+    Sequence,
+
+    /// The PIN looks like a date or a year (like `0512`, `1984`, or `31121999`).
+    ///
+    /// Dates are only detected in PINs of 4, 6, or 8 digits.
+    Date,
+
+    /// The PIN is one of the most frequently used PINs (like `2580`).
+    Common,
+}
+
+impl Display for PinPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Repeated => "repeated digits",
+            Self::Sequence => "a sequence of digits",
+            Self::Date => "a date",
+            Self::Common => "a commonly used PIN",
+        })
+    }
+}
+
+/// Is the given `day` of the given `month` a valid date (in a leap year)?
+fn is_day_of_month(day: u8, month: u8) -> bool {
+    const DAYS: [u8; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    (1..=12).contains(&month) && (1..=DAYS[month as usize - 1]).contains(&day)
+}
+
+/// Is the given 4-digit number a plausible year?
+fn is_year(year: u16) -> bool {
+    (1900..=2099).contains(&year)
+}
+
+/// Does the PIN of the given `digits` look like a date or a year?
+fn is_date(digits: &[u8]) -> bool {
+    let pair = |i: usize| digits[i] * 10 + digits[i + 1];
+    let year = |i: usize| pair(i) as u16 * 100 + pair(i + 2) as u16;
+    match digits.len() {
+        4 => {
+            is_day_of_month(pair(0), pair(2))
+                || is_day_of_month(pair(2), pair(0))
+                || is_year(year(0))
+        }
+        // DDMMYY, MMDDYY, or YYMMDD
+        6 => {
+            is_day_of_month(pair(0), pair(2))
+                || is_day_of_month(pair(2), pair(0))
+                || is_day_of_month(pair(4), pair(2))
+        }
+        // DDMMYYYY, MMDDYYYY, or YYYYMMDD
+        8 => {
+            (is_year(year(4))
+                && (is_day_of_month(pair(0), pair(2)) || is_day_of_month(pair(2), pair(0))))
+                || (is_year(year(0)) && is_day_of_month(pair(6), pair(4)))
+        }
+        _ => false,
+    }
+}
+
+/// Find the weak pattern (if any) of the given `pin`.
+///
+/// A PIN that contains anything other than ASCII digits has no weak pattern.
+///
+/// ```rust
+/// use mk_pass::{PinPattern, weak_pin_pattern};
+/// assert_eq!(weak_pin_pattern("1111"), Some(PinPattern::Repeated));
+/// assert_eq!(weak_pin_pattern("8901"), Some(PinPattern::Sequence));
+/// assert_eq!(weak_pin_pattern("2512"), Some(PinPattern::Date));
+/// assert_eq!(weak_pin_pattern("2580"), Some(PinPattern::Common));
+/// assert_eq!(weak_pin_pattern("8375"), None);
+/// ```
+pub fn weak_pin_pattern(pin: &str) -> Option<PinPattern> {
+    let digits = pin
+        .bytes()
+        .map(|b| b.is_ascii_digit().then(|| b - b'0'))
+        .collect::<Option<Vec<u8>>>()?;
+    let len = digits.len();
+    if len == 0 {
+        return None;
+    }
+    if (1..len).any(|block| len % block == 0 && digits[block..] == digits[..len - block]) {
+        return Some(PinPattern::Repeated);
+    }
+    let step = |delta: u8| digits.windows(2).all(|w| (w[0] + delta) % 10 == w[1]);
+    if len > 1 && (step(1) || step(9)) {
+        return Some(PinPattern::Sequence);
+    }
+    if is_date(&digits) {
+        return Some(PinPattern::Date);
+    }
+    if COMMON_PINS.contains(&pin) {
+        return Some(PinPattern::Common);
+    }
+    None
+}
+
+/// A structure to describe PIN requirements.
+#[cfg_attr(feature = "clap", derive(Args))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinRequirements {
+    /// How many digits should the PIN contain?
+    #[cfg_attr(feature = "clap", arg(long, short, default_value = "6"))]
+    pub length: u16,
+
+    /// Allow digits to be used more than once?
+    #[cfg_attr(
+        feature = "clap",
+        arg(
+            short = 'r',
+            long,
+            help = "Allow digits to be used more than once.",
+            long_help = "Allow digits to be used more than once.\
+            \n\nBy default, each digit is only used once (limiting the length to 10).\n\
+            PINs made of a single repeated digit (like `1111`) are always rejected.",
+            action = ArgAction::SetTrue
+        )
+    )]
+    pub allow_repeats: bool,
+}
+
+impl Default for PinRequirements {
+    /// Create default PIN requirements.
+    fn default() -> Self {
+        Self {
+            length: 6,
+            allow_repeats: false,
+        }
+    }
+}
+
+impl PinRequirements {
+    /// Validates the instance's values.
+    ///
+    /// This returns a mutated copy of the instance where the `length` is not less than 4
+    /// and (unless [`PinRequirements::allow_repeats`] is enabled) not more than 10.
+    ///
+    /// ```rust
+    /// use mk_pass::PinRequirements;
+    /// let req = PinRequirements { length: 12, allow_repeats: false };
+    /// assert_eq!(req.validate().length, 10);
+    /// ```
+    pub fn validate(&self) -> Self {
+        let mut length = self.length.max(MIN_PIN_LENGTH);
+        if !self.allow_repeats {
+            length = length.min(DECIMAL.len() as u16);
+        }
+        Self { length, ..*self }
+    }
+}
+
+/// Generate a numeric PIN given the constraints specified by `config`.
+///
+/// This function will invoke [`PinRequirements::validate()`] to ensure basic
+/// PIN requirements are met. PINs with a weak pattern (see [`weak_pin_pattern()`])
+/// are never generated.
+///
+/// ```rust
+/// use mk_pass::{PinRequirements, generate_pin, weak_pin_pattern};
+/// let pin = generate_pin(PinRequirements::default());
+/// assert_eq!(pin.len(), 6);
+/// assert!(pin.chars().all(|ch| ch.is_ascii_digit()));
+/// assert_eq!(weak_pin_pattern(&pin), None);
+/// ```
+pub fn generate_pin(config: PinRequirements) -> String {
+    generate_pin_with_rng(config, &mut rand::rng())
+}
+
+/// Generate a numeric PIN given the constraints specified by `config` using the given `rng`.
+///
+/// See also [`generate_pin()`].
+pub fn generate_pin_with_rng<R: CryptoRng + Rng>(config: PinRequirements, rng: &mut R) -> String {
+    let config = config.validate();
+    let length = config.length as usize;
+    loop {
+        let pin = if config.allow_repeats {
+            (0..length)
+                .map(|_| DECIMAL[rng.random_range(0..DECIMAL.len())])
+                .collect::<String>()
+        } else {
+            let mut indices = sample(rng, DECIMAL.len(), length).into_vec();
+            indices.shuffle(rng);
+            indices.into_iter().map(|i| DECIMAL[i]).collect()
+        };
+        if weak_pin_pattern(&pin).is_none() {
+            return pin;
+        }
+    }
+}
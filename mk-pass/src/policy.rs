@@ -0,0 +1,124 @@
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{PasswordRequirements, PolicyError};
+
+/// The format of a policy file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl PolicyFormat {
+    /// Get the format of the file at the given `path` from its extension (if recognized).
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// A collection of named [`PasswordRequirements`] (profiles).
+///
+/// In a policy file, each profile is a table (or object) of requirements
+/// using camelCase names (like `maxDecimal`).
+/// Any requirement not specified in a profile uses its default value.
+///
+/// ```rust
+/// use mk_pass::{Policy, PolicyFormat};
+/// let text = r#"
+/// [database]
+/// length = 32
+/// specials = 0
+///
+/// [api-keys]
+/// length = 64
+/// allowRepeats = true
+/// "#;
+/// let policy = Policy::parse(text, PolicyFormat::Toml).unwrap();
+/// assert_eq!(policy.profiles().collect::<Vec<&str>>(), ["api-keys", "database"]);
+/// let database = policy.profile(Some("database")).unwrap();
+/// assert_eq!(database.length, 32);
+/// assert_eq!(database.specials, 0);
+/// assert_eq!(database.decimal, 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Policy {
+    profiles: BTreeMap<String, PasswordRequirements>,
+}
+
+impl Policy {
+    /// The name of the profile used when no profile is specified.
+    pub const DEFAULT_PROFILE: &str = "default";
+
+    /// Parse a policy from the given `text` in the given `format`.
+    pub fn parse(text: &str, format: PolicyFormat) -> Result<Self, PolicyError> {
+        let parsed = match format {
+            PolicyFormat::Toml => toml::from_str(text).map_err(|e| e.to_string()),
+            PolicyFormat::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+            PolicyFormat::Yaml => serde_yaml_ng::from_str(text).map_err(|e| e.to_string()),
+        };
+        parsed.map_err(|message| PolicyError::Parse { message })
+    }
+
+    /// Read and [parse](Policy::parse()) a policy from the file at the given `path`.
+    ///
+    /// The format of the file is determined by its extension
+    /// (`.toml`, `.json`, `.yaml`, or `.yml`).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PolicyError> {
+        let path = path.as_ref();
+        let format = PolicyFormat::from_path(path).ok_or_else(|| PolicyError::UnknownFormat {
+            path: path.to_path_buf(),
+        })?;
+        let text = std::fs::read_to_string(path).map_err(|e| PolicyError::Read {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+        Self::parse(&text, format)
+    }
+
+    /// Add (or replace) a profile with the given `name` and `requirements`.
+    pub fn insert(&mut self, name: impl Into<String>, requirements: PasswordRequirements) {
+        self.profiles.insert(name.into(), requirements);
+    }
+
+    /// The names of the profiles in this policy (in alphabetical order).
+    pub fn profiles(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Get the requirements of the profile with the given `name` (if any).
+    pub fn get(&self, name: &str) -> Option<PasswordRequirements> {
+        self.profiles.get(name).copied()
+    }
+
+    /// Select a profile from this policy.
+    ///
+    /// If a `name` is given, then that profile is selected.
+    /// Otherwise, the [`Policy::DEFAULT_PROFILE`] is selected, or
+    /// the only profile if this policy has exactly 1 profile.
+    pub fn profile(&self, name: Option<&str>) -> Result<PasswordRequirements, PolicyError> {
+        let available = || self.profiles().map(str::to_string).collect();
+        match name {
+            Some(name) => self.get(name).ok_or_else(|| PolicyError::UnknownProfile {
+                name: name.to_string(),
+                available: available(),
+            }),
+            None => match self.get(Self::DEFAULT_PROFILE) {
+                Some(requirements) => Ok(requirements),
+                None if self.profiles.len() == 1 => Ok(*self.profiles.values().next().unwrap()),
+                None => Err(PolicyError::NoDefaultProfile {
+                    available: available(),
+                }),
+            },
+        }
+    }
+}
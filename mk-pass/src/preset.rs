@@ -0,0 +1,136 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::PasswordRequirements;
+
+/// A named set of password requirements derived from a published standard.
+///
+/// Use [`PasswordRequirements::preset()`] to get the requirements of a preset.
+///
+/// Standards that forbid composition rules (like NIST SP 800-63B) are encoded
+/// with a minimum count of `0` and no effective maximum count for decimal integers
+/// and special characters. Likewise, repeated characters are allowed and the first
+/// character is not required to be a letter unless the standard says otherwise.
+///
+/// Note that generated passwords always contain at least 1 uppercase and
+/// 1 lowercase letter, regardless of the preset.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// NIST Special Publication 800-63B (revision 4), section 3.1.1.2.
+    ///
+    /// Passwords used as a single factor of authentication shall be at least
+    /// 15 characters long, and verifiers shall not impose composition rules.
+    #[cfg_attr(feature = "clap", value(name = "nist-800-63b"))]
+    Nist80063b,
+
+    /// PCI DSS (version 4.0.1), requirement 8.3.6.
+    ///
+    /// Passwords shall be at least 12 characters long and contain both
+    /// numeric and alphabetic characters.
+    #[cfg_attr(feature = "clap", value(name = "pci-dss"))]
+    PciDss,
+
+    /// CIS Critical Security Controls (version 8), safeguard 5.2.
+    ///
+    /// Passwords for accounts that do not use multi-factor authentication
+    /// shall be at least 14 characters long.
+    #[cfg_attr(feature = "clap", value(name = "cis"))]
+    Cis,
+
+    /// OWASP Application Security Verification Standard (version 4.0.3),
+    /// requirements 2.1.1 and 2.1.9.
+    ///
+    /// Passwords shall be at least 12 characters long, and there shall be
+    /// no composition rules.
+    #[cfg_attr(feature = "clap", value(name = "owasp-asvs"))]
+    OwaspAsvs,
+}
+
+impl Preset {
+    /// All the available presets.
+    pub const ALL: [Preset; 4] = [Self::Nist80063b, Self::PciDss, Self::Cis, Self::OwaspAsvs];
+
+    /// The name of this preset (as accepted by the `--preset` CLI option).
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Nist80063b => "nist-800-63b",
+            Self::PciDss => "pci-dss",
+            Self::Cis => "cis",
+            Self::OwaspAsvs => "owasp-asvs",
+        }
+    }
+
+    /// The password requirements that this preset encodes.
+    ///
+    /// See also [`PasswordRequirements::preset()`].
+    pub const fn requirements(&self) -> PasswordRequirements {
+        // no composition rules: any count of decimal integers and special characters
+        let unrestricted = PasswordRequirements {
+            length: 16,
+            decimal: 0,
+            max_decimal: Some(u16::MAX),
+            specials: 0,
+            max_specials: Some(u16::MAX),
+            uppercase: 0,
+            max_uppercase: None,
+            lowercase: 0,
+            max_lowercase: None,
+            first_is_letter: false,
+            allow_repeats: true,
+        };
+        match self {
+            Self::Nist80063b => PasswordRequirements {
+                length: 15,
+                ..unrestricted
+            },
+            Self::PciDss => PasswordRequirements {
+                length: 12,
+                decimal: 1,
+                ..unrestricted
+            },
+            Self::Cis => PasswordRequirements {
+                length: 14,
+                ..unrestricted
+            },
+            Self::OwaspAsvs => PasswordRequirements {
+                length: 12,
+                ..unrestricted
+            },
+        }
+    }
+}
+
+impl Display for Preset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Preset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names = Self::ALL.map(|preset| preset.name()).join(", ");
+                format!("Unknown preset '{s}' (available presets are: {names})")
+            })
+    }
+}
+
+impl PasswordRequirements {
+    /// The password requirements of the given `preset`.
+    ///
+    /// ```rust
+    /// use mk_pass::{PasswordRequirements, Preset};
+    /// let config = PasswordRequirements::preset(Preset::PciDss);
+    /// assert_eq!(config.length, 12);
+    /// assert_eq!(config.decimal, 1);
+    /// assert!(config.check().is_ok());
+    /// ```
+    pub const fn preset(preset: Preset) -> Self {
+        preset.requirements()
+    }
+}
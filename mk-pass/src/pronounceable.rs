@@ -0,0 +1,265 @@
+use rand::{prelude::*, seq::index::sample};
+
+use crate::{CharacterSets, PasswordRequirements, RequirementsError, entropy::log2_factorial};
+
+/// The consonant clusters used to build pronounceable passwords.
+const CONSONANTS: [&str; 30] = [
+    "b", "c", "d", "f", "g", "h", "j", "k", "l", "m", "n", "p", "r", "s", "t", "v", "w", "x", "y",
+    "z", "br", "ch", "cr", "dr", "gr", "ph", "pl", "sh", "st", "th",
+];
+
+/// The vowel clusters used to build pronounceable passwords.
+const VOWELS: [&str; 12] = [
+    "a", "e", "i", "o", "u", "ai", "ea", "ee", "ie", "oa", "oo", "ou",
+];
+
+/// Calculate `log2(n! / (k! * (n - k)!))`, the number of ways to choose `k` of `n` items.
+fn log2_binomial(n: u16, k: u16) -> f64 {
+    log2_factorial(n) - log2_factorial(k) - log2_factorial(n - k)
+}
+
+/// The clusters of the given kind (vowels or consonants) that fit in the `remaining` letters.
+///
+/// Clusters with letters that are not in the `lowercase` set are skipped.
+fn clusters(lowercase: &[char], vowel: bool, remaining: usize) -> Vec<&'static str> {
+    let all: &[&str] = if vowel { &VOWELS } else { &CONSONANTS };
+    all.iter()
+        .copied()
+        .filter(|c| c.len() <= remaining && c.chars().all(|ch| lowercase.contains(&ch)))
+        .collect()
+}
+
+/// Generate a pronounceable password given the constraints specified by `config`.
+///
+/// The letters alternate between consonant clusters (like `b` or `th`) and
+/// vowel clusters (like `a` or `ou`), in the style of `pwgen`.
+/// The required number of [`PasswordRequirements::decimal`] integers and
+/// [`PasswordRequirements::specials`] characters are inserted at random positions
+/// (after the first letter if [`PasswordRequirements::first_is_letter`] is enabled),
+/// and at least [`PasswordRequirements::uppercase`] letters (1 or more) are capitalized.
+///
+/// The `config` is validated with [`PasswordRequirements::allow_repeats`] enabled
+/// because pronounceable letters are often repeated,
+/// so any character may be repeated even if the `config` disables it.
+/// The maximum counts (like [`PasswordRequirements::max_decimal`]) are ignored.
+///
+/// Pronounceable passwords have much less entropy than passwords generated by
+/// [`generate_password()`](crate::generate_password) of the same length.
+/// Use [`pronounceable_entropy()`] to estimate it.
+///
+/// ```rust
+/// use mk_pass::{PasswordRequirements, generate_pronounceable_password};
+/// let password = generate_pronounceable_password(PasswordRequirements::default());
+/// assert_eq!(password.len(), 16);
+/// ```
+pub fn generate_pronounceable_password(config: PasswordRequirements) -> String {
+    CharacterSets::default().generate_pronounceable_password(config)
+}
+
+/// Generate a pronounceable password given the constraints specified by `config` using the given `rng`.
+///
+/// See also [`generate_pronounceable_password()`].
+pub fn generate_pronounceable_password_with_rng<R: CryptoRng + Rng>(
+    config: PasswordRequirements,
+    rng: &mut R,
+) -> String {
+    CharacterSets::default().generate_pronounceable_password_with_rng(config, rng)
+}
+
+/// Calculate the entropy (in bits) of a pronounceable password generated with the given `config`.
+///
+/// This is the entropy of the random choices made by [`generate_pronounceable_password()`]:
+/// the consonant and vowel clusters, the capitalized letters,
+/// and the positions and values of the decimal integers and special characters.
+///
+/// ```rust
+/// use mk_pass::{PasswordRequirements, password_entropy, pronounceable_entropy};
+/// let config = PasswordRequirements::default();
+/// assert!(pronounceable_entropy(config) < password_entropy(config));
+/// ```
+pub fn pronounceable_entropy(config: PasswordRequirements) -> f64 {
+    CharacterSets::default().pronounceable_entropy(config)
+}
+
+impl CharacterSets {
+    /// Check that the lowercase letters include at least one single-letter consonant cluster
+    /// and one single-letter vowel cluster, so that any number of letters can be pronounced.
+    pub(crate) fn check_pronounceable(&self) -> Result<(), RequirementsError> {
+        let pronounceable = [false, true]
+            .into_iter()
+            .all(|vowel| !clusters(self.lowercase(), vowel, 1).is_empty());
+        if pronounceable {
+            Ok(())
+        } else {
+            Err(RequirementsError::NotPronounceable)
+        }
+    }
+
+    /// Validate the `config` for pronounceable passwords.
+    fn validate_pronounceable(&self, config: PasswordRequirements) -> PasswordRequirements {
+        let count = |count: u16, set: &[char]| if set.is_empty() { 0 } else { count };
+        PasswordRequirements {
+            decimal: count(config.decimal, self.decimal()),
+            specials: count(config.specials, self.special()),
+            max_decimal: None,
+            max_specials: None,
+            allow_repeats: true,
+            ..config
+        }
+        .validate_with(self)
+    }
+
+    /// The number of letters and capitalized letters in a pronounceable password.
+    fn pronounceable_letters(config: PasswordRequirements) -> (u16, u16) {
+        let letters = config.length - config.decimal - config.specials;
+        let capitals = config
+            .uppercase
+            .max(1)
+            .min(letters.saturating_sub(config.lowercase.max(1)));
+        (letters, capitals)
+    }
+
+    /// Generate a pronounceable password using the decimal integers and special characters
+    /// of these character sets given the constraints specified by `config`.
+    ///
+    /// The letters are always built from ASCII consonant and vowel clusters,
+    /// skipping clusters with letters that are not in these character sets.
+    /// Like [`generate_pronounceable_password()`], any character may be repeated
+    /// regardless of [`PasswordRequirements::allow_repeats`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`CharacterSets::lowercase()`] set does not include
+    /// a single-letter consonant cluster (like `b`) and a single-letter vowel cluster (like `a`).
+    /// Use [`CharacterSets::try_generate_pronounceable_password()`] to get an error instead.
+    pub fn generate_pronounceable_password(&self, config: PasswordRequirements) -> String {
+        self.generate_pronounceable_password_with_rng(config, &mut rand::rng())
+    }
+
+    /// Generate a pronounceable password using the decimal integers and special characters
+    /// of these character sets given the constraints specified by `config`.
+    ///
+    /// This behaves like [`CharacterSets::generate_pronounceable_password()`], but returns
+    /// [`RequirementsError::NotPronounceable`] instead of panicking.
+    ///
+    /// ```rust
+    /// use mk_pass::{CharacterSets, PasswordRequirements, RequirementsError};
+    /// let sets = CharacterSets::default().exclude("aeiou".chars());
+    /// assert_eq!(
+    ///     sets.try_generate_pronounceable_password(PasswordRequirements::default()),
+    ///     Err(RequirementsError::NotPronounceable)
+    /// );
+    /// ```
+    pub fn try_generate_pronounceable_password(
+        &self,
+        config: PasswordRequirements,
+    ) -> Result<String, RequirementsError> {
+        self.check_pronounceable()?;
+        Ok(self.generate_pronounceable_password(config))
+    }
+
+    /// Generate a pronounceable password using the decimal integers and special characters
+    /// of these character sets given the constraints specified by `config` using the given `rng`.
+    ///
+    /// See also [`generate_pronounceable_password()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`CharacterSets::generate_pronounceable_password()`].
+    pub fn generate_pronounceable_password_with_rng<R: CryptoRng + Rng>(
+        &self,
+        config: PasswordRequirements,
+        rng: &mut R,
+    ) -> String {
+        if let Err(err) = self.check_pronounceable() {
+            panic!("{err}");
+        }
+        let config = self.validate_pronounceable(config);
+        let (letters, capitals) = Self::pronounceable_letters(config);
+
+        // alternate between consonant and vowel clusters
+        let mut word = Vec::with_capacity(letters as usize);
+        let mut vowel = rng.random::<bool>();
+        while word.len() < letters as usize {
+            let choices = clusters(self.lowercase(), vowel, letters as usize - word.len());
+            let cluster = choices[rng.random_range(0..choices.len())];
+            word.extend(cluster.chars());
+            vowel = !vowel;
+        }
+        // only capitalize letters that are in the uppercase set
+        let candidates = (0..word.len())
+            .filter(|i| self.uppercase().contains(&word[*i].to_ascii_uppercase()))
+            .collect::<Vec<_>>();
+        let capitals = (capitals as usize).min(candidates.len());
+        for index in sample(rng, candidates.len(), capitals) {
+            let letter = &mut word[candidates[index]];
+            *letter = letter.to_ascii_uppercase();
+        }
+
+        // insert the decimal integers and special characters at random positions
+        let others = (config.decimal + config.specials) as usize;
+        let skip = usize::from(config.first_is_letter);
+        let length = config.length as usize;
+        let mut positions = sample(rng, length - skip, others)
+            .into_iter()
+            .map(|i| i + skip)
+            .collect::<Vec<_>>();
+        positions.shuffle(rng);
+        let mut password = vec![None; length];
+        for (i, position) in positions.into_iter().enumerate() {
+            let set = if i < config.decimal as usize {
+                self.decimal()
+            } else {
+                self.special()
+            };
+            password[position] = Some(set[rng.random_range(0..set.len())]);
+        }
+        let mut word = word.into_iter();
+        password
+            .into_iter()
+            .map(|ch| ch.or_else(|| word.next()).unwrap_or_default())
+            .collect()
+    }
+
+    /// Calculate the entropy (in bits) of a pronounceable password generated
+    /// with these character sets and the given `config`.
+    ///
+    /// If some ASCII letters are not in these character sets, then this is an upper bound
+    /// because fewer letters can be capitalized.
+    /// If no pronounceable password can be generated (see
+    /// [`CharacterSets::try_generate_pronounceable_password()`]), then this is `0`.
+    ///
+    /// See also [`pronounceable_entropy()`].
+    pub fn pronounceable_entropy(&self, config: PasswordRequirements) -> f64 {
+        if self.check_pronounceable().is_err() {
+            return 0.0;
+        }
+        let config = self.validate_pronounceable(config);
+        let (letters, capitals) = Self::pronounceable_letters(config);
+
+        // the expected entropy of the clusters that fill the remaining letters,
+        // starting with a vowel (index 1) or a consonant (index 0)
+        let mut words = vec![[0.0f64; 2]; letters as usize + 1];
+        for remaining in 1..=letters as usize {
+            for (index, vowel) in [false, true].into_iter().enumerate() {
+                let choices = clusters(self.lowercase(), vowel, remaining);
+                let count = choices.len() as f64;
+                let next = choices
+                    .iter()
+                    .map(|c| words[remaining - c.len()][1 - index])
+                    .sum::<f64>();
+                words[remaining][index] = count.log2() + next / count;
+            }
+        }
+        let [consonant_first, vowel_first] = words[letters as usize];
+        let word = 1.0 + (consonant_first + vowel_first) / 2.0;
+
+        let others = config.decimal + config.specials;
+        let positions = config.length - u16::from(config.first_is_letter);
+        word + log2_binomial(letters, capitals)
+            + log2_binomial(positions, others)
+            + log2_binomial(others, config.decimal)
+            + config.decimal as f64 * (self.decimal().len() as f64).log2()
+            + config.specials as f64 * (self.special().len() as f64).log2()
+    }
+}
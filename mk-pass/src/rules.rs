@@ -0,0 +1,369 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    CharacterSets, DECIMAL, LOWERCASE, PasswordRequirements, PasswordRulesError, UPPERCASE,
+    config::MIN_LENGTH, helpers::CharKind,
+};
+
+/// The characters of the `special` class: ASCII punctuation and the space character.
+fn special_class() -> impl Iterator<Item = char> {
+    (' '..='~').filter(|ch| !ch.is_ascii_alphanumeric())
+}
+
+/// Get the kind of an ASCII printable character.
+fn classify(ch: char) -> Result<CharKind, PasswordRulesError> {
+    match ch {
+        'A'..='Z' => Ok(CharKind::Uppercase),
+        'a'..='z' => Ok(CharKind::Lowercase),
+        '0'..='9' => Ok(CharKind::Decimal),
+        ' '..='~' => Ok(CharKind::Special),
+        _ => Err(PasswordRulesError::UnsupportedCharacter { ch }),
+    }
+}
+
+/// Split the `text` at each `separator` that is not inside a custom character class.
+///
+/// A `]` only closes a custom character class if it is not the class' first character.
+fn split_outside_classes(text: &str, separator: char) -> Result<Vec<&str>, PasswordRulesError> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut class_start = None;
+    for (i, ch) in text.char_indices() {
+        match class_start {
+            None if ch == '[' => class_start = Some(i),
+            None if ch == separator => {
+                parts.push(&text[start..i]);
+                start = i + ch.len_utf8();
+            }
+            Some(open) if ch == ']' && i > open + 1 => class_start = None,
+            _ => {}
+        }
+    }
+    if let Some(open) = class_start {
+        return Err(PasswordRulesError::UnterminatedClass {
+            class: text[open..].to_string(),
+        });
+    }
+    parts.push(&text[start..]);
+    Ok(parts)
+}
+
+/// Get the characters of a named or custom character class.
+fn class_chars(class: &str) -> Result<Vec<char>, PasswordRulesError> {
+    match class.to_ascii_lowercase().as_str() {
+        "upper" => Ok(UPPERCASE.to_vec()),
+        "lower" => Ok(LOWERCASE.to_vec()),
+        "digit" => Ok(DECIMAL.to_vec()),
+        "special" => Ok(special_class().collect()),
+        "ascii-printable" => Ok((' '..='~').collect()),
+        _ => match class.strip_prefix('[').and_then(|c| c.strip_suffix(']')) {
+            Some(body) if !body.is_empty() => {
+                body.chars().map(|ch| classify(ch).map(|_| ch)).collect()
+            }
+            _ => Err(PasswordRulesError::UnknownClass {
+                name: class.to_string(),
+            }),
+        },
+    }
+}
+
+/// Get the characters of a `,` separated list of character classes.
+fn classes_chars(classes: &str) -> Result<Vec<char>, PasswordRulesError> {
+    let mut chars = Vec::new();
+    for class in split_outside_classes(classes, ',')? {
+        chars.extend(class_chars(class.trim())?);
+    }
+    Ok(chars)
+}
+
+/// Get the name of a character class that describes the given `set` of the given `kind`.
+fn class_name(kind: CharKind, set: &[char]) -> String {
+    let named = match kind {
+        CharKind::Uppercase => ("upper", UPPERCASE.to_vec()),
+        CharKind::Lowercase => ("lower", LOWERCASE.to_vec()),
+        CharKind::Decimal => ("digit", DECIMAL.to_vec()),
+        CharKind::Special => ("special", special_class().collect()),
+    };
+    if set.len() == named.1.len() && named.1.iter().all(|ch| set.contains(ch)) {
+        return named.0.to_string();
+    }
+    // a `]` is only literal as the first character of a custom class
+    let mut class = String::from("[");
+    if set.contains(&']') {
+        class.push(']');
+    }
+    class.extend(set.iter().filter(|ch| **ch != ']'));
+    class.push(']');
+    class
+}
+
+/// Parse the positive integer `value` of the given `property`.
+fn number(property: &str, value: &str) -> Result<u16, PasswordRulesError> {
+    value
+        .parse::<u16>()
+        .ok()
+        .filter(|number| *number > 0)
+        .ok_or_else(|| PasswordRulesError::InvalidNumber {
+            property: property.to_string(),
+            value: value.to_string(),
+        })
+}
+
+/// Password requirements and character sets described by a password rules string.
+///
+/// The password rules syntax is used by the `passwordrules` attribute of HTML input elements.
+/// It is a list of `name: value` properties separated by `;`.
+/// The supported properties are
+///
+/// - `minlength` and `maxlength`: the [`PasswordRequirements::length`] is
+///   the minimum length (or the default length limited by the maximum length).
+///   Passwords are never shorter than 10 characters, so a smaller `maxlength` is an error.
+///   A `minlength` that the allowed characters cannot fill
+///   (see [`PasswordRequirements::validate_with()`]) is also an error.
+/// - `required` and `allowed`: a list of character classes separated by `,`.
+///   The character classes are `upper`, `lower`, `digit`, `special`,
+///   `ascii-printable`, and custom classes like `[-().&@?'#,/"+]`.
+///   A `]` can be included in a custom class as its first character.
+/// - `max-consecutive`: any positive integer disables
+///   [`PasswordRequirements::allow_repeats`] (so no character is repeated at all,
+///   which satisfies every limit of consecutive characters).
+///
+/// If no classes are specified, then all ASCII printable characters are allowed.
+/// Each `required` property is satisfied by requiring at least 1 character of a kind
+/// (uppercase letters, lowercase letters, decimal integers, or special characters)
+/// whose allowed characters all belong to the required classes.
+///
+/// Any other property or character class (like `unicode`) is reported as an error.
+///
+/// ```rust
+/// use mk_pass::PasswordRules;
+/// let rules: PasswordRules = "minlength: 20; required: lower; required: [-().&@?'#,/\"+]; allowed: digit"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(rules.requirements.length, 20);
+/// assert_eq!(rules.requirements.specials, 1);
+/// assert!(rules.sets.uppercase().is_empty());
+/// assert_eq!(rules.sets.special().len(), 13);
+///
+/// let password = rules.sets.generate_password(rules.requirements);
+/// assert_eq!(password.len(), 20);
+/// assert_eq!(
+///     rules.to_string(),
+///     "minlength: 20; required: lower; required: [-().&@?'#,/\"+]; allowed: digit"
+/// );
+/// ```
+///
+/// The [`Display`] implementation serializes the rules.
+/// The `required` classes, `maxlength`, and `max-consecutive` of parsed rules are serialized
+/// as given.
+/// Note that counts other than `0` or `1` (like [`PasswordRequirements::decimal`] `= 3`)
+/// and [`PasswordRequirements::first_is_letter`] cannot be expressed in this syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordRules {
+    /// The requirements of a password's contents.
+    pub requirements: PasswordRequirements,
+
+    /// The characters allowed in a password.
+    pub sets: CharacterSets,
+
+    /// The lists of character classes of each `required` property.
+    required: Vec<String>,
+
+    /// The `maxlength` property.
+    max_length: Option<u16>,
+
+    /// The `max-consecutive` property (if [`PasswordRequirements::allow_repeats`] is disabled).
+    max_consecutive: u16,
+}
+
+impl PasswordRules {
+    /// Create password rules from the given `requirements` and character `sets`.
+    ///
+    /// Any allowed letters are required because generated passwords
+    /// always contain at least 1 uppercase and 1 lowercase letter (if available).
+    pub fn new(requirements: PasswordRequirements, sets: CharacterSets) -> Self {
+        let required = [
+            (CharKind::Uppercase, requirements.max_uppercase != Some(0)),
+            (CharKind::Lowercase, requirements.max_lowercase != Some(0)),
+            (CharKind::Decimal, requirements.decimal > 0),
+            (CharKind::Special, requirements.specials > 0),
+        ]
+        .into_iter()
+        .filter(|&(kind, is_required)| is_required && !sets.get(kind).is_empty())
+        .map(|(kind, _)| class_name(kind, sets.get(kind)))
+        .collect();
+        Self {
+            requirements,
+            sets,
+            required,
+            max_length: None,
+            max_consecutive: 1,
+        }
+    }
+
+    /// Parse password rules from the given `rules` string.
+    ///
+    /// The resulting [`PasswordRequirements`] are not validated.
+    pub fn parse(rules: &str) -> Result<Self, PasswordRulesError> {
+        let mut min_length = None;
+        let mut max_length = None;
+        let mut max_consecutive = None;
+        let mut allowed = Vec::new();
+        let mut required = Vec::new();
+        for clause in split_outside_classes(rules, ';')? {
+            let clause = clause.trim();
+            if clause.is_empty() {
+                continue;
+            }
+            let (name, value) =
+                clause
+                    .split_once(':')
+                    .ok_or_else(|| PasswordRulesError::MissingValue {
+                        clause: clause.to_string(),
+                    })?;
+            let name = name.trim().to_ascii_lowercase();
+            let value = value.trim();
+            match name.as_str() {
+                "required" | "allowed" => {
+                    let chars = classes_chars(value)?;
+                    allowed.extend(&chars);
+                    if name == "required" {
+                        required.push((value.to_string(), chars));
+                    }
+                }
+                "minlength" => min_length = Some(number(&name, value)?),
+                "maxlength" => max_length = Some(number(&name, value)?),
+                // there is no requirement for runs of repeated characters,
+                // so any limit is satisfied by not repeating characters at all
+                "max-consecutive" => max_consecutive = Some(number(&name, value)?),
+                _ => return Err(PasswordRulesError::UnknownProperty { name }),
+            }
+        }
+        if allowed.is_empty() {
+            allowed.extend(' '..='~');
+        }
+
+        let (mut uppercase, mut lowercase, mut decimal, mut special) =
+            (vec![], vec![], vec![], vec![]);
+        for ch in allowed {
+            match classify(ch)? {
+                CharKind::Uppercase => uppercase.push(ch),
+                CharKind::Lowercase => lowercase.push(ch),
+                CharKind::Decimal => decimal.push(ch),
+                CharKind::Special => special.push(ch),
+            }
+        }
+        if uppercase.is_empty() && lowercase.is_empty() {
+            return Err(PasswordRulesError::NoLetters);
+        }
+
+        let mut decimal_required = false;
+        let mut specials_required = false;
+        for (class, chars) in &required {
+            let covers = |set: &[char]| !set.is_empty() && set.iter().all(|ch| chars.contains(ch));
+            if covers(&uppercase) || covers(&lowercase) {
+                // generated passwords always contain at least 1 letter of each available kind
+                continue;
+            } else if covers(&decimal) {
+                decimal_required = true;
+            } else if covers(&special) {
+                specials_required = true;
+            } else {
+                return Err(PasswordRulesError::UnsupportedRequirement {
+                    class: class.clone(),
+                });
+            }
+        }
+
+        // passwords are never shorter than `MIN_LENGTH`
+        let minimum = min_length.unwrap_or(MIN_LENGTH).max(MIN_LENGTH);
+        let length = min_length.unwrap_or(PasswordRequirements::default().length);
+        let length = match max_length {
+            Some(maximum) if maximum < minimum => {
+                return Err(PasswordRulesError::InvalidLength { minimum, maximum });
+            }
+            Some(maximum) => length.clamp(minimum, maximum),
+            None => length.max(minimum),
+        };
+        // allowed (but not required) characters have no maximum count
+        let requirements = PasswordRequirements {
+            length,
+            decimal: u16::from(decimal_required),
+            max_decimal: (!decimal.is_empty()).then_some(u16::MAX),
+            specials: u16::from(specials_required),
+            max_specials: (!special.is_empty()).then_some(u16::MAX),
+            first_is_letter: false,
+            allow_repeats: max_consecutive.is_none(),
+            ..Default::default()
+        };
+        let sets = CharacterSets::default()
+            .with_uppercase(uppercase)
+            .with_lowercase(lowercase)
+            .with_decimal(decimal)
+            .with_special(special);
+        // report a length that validation would shorten (instead of silently shortening it)
+        let maximum = requirements.validate_with(&sets).length;
+        if maximum < length {
+            return Err(PasswordRulesError::UnreachableLength {
+                minimum: length,
+                maximum,
+            });
+        }
+        Ok(Self {
+            requirements,
+            sets,
+            required: required.into_iter().map(|(class, _)| class).collect(),
+            max_length,
+            max_consecutive: max_consecutive.unwrap_or(1),
+        })
+    }
+}
+
+impl FromStr for PasswordRules {
+    type Err = PasswordRulesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for PasswordRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = &self.requirements;
+        let mut clauses = vec![format!("minlength: {}", config.length)];
+        if let Some(maximum) = self.max_length {
+            clauses.push(format!("maxlength: {}", maximum.max(config.length)));
+        }
+        let mut required = Vec::new();
+        for classes in &self.required {
+            clauses.push(format!("required: {classes}"));
+            required.extend(classes_chars(classes).unwrap_or_default());
+        }
+        // the allowed characters that are not in a required class
+        let mut allowed = Vec::new();
+        for (kind, is_allowed) in [
+            (CharKind::Uppercase, config.max_uppercase != Some(0)),
+            (CharKind::Lowercase, config.max_lowercase != Some(0)),
+            (
+                CharKind::Decimal,
+                config.max_decimal.unwrap_or(config.decimal) > 0,
+            ),
+            (
+                CharKind::Special,
+                config.max_specials.unwrap_or(config.specials) > 0,
+            ),
+        ] {
+            let set = self.sets.get(kind);
+            if is_allowed && set.iter().any(|ch| !required.contains(ch)) {
+                allowed.push(class_name(kind, set));
+            }
+        }
+        if !allowed.is_empty() {
+            clauses.push(format!("allowed: {}", allowed.join(", ")));
+        }
+        if !config.allow_repeats {
+            clauses.push(format!("max-consecutive: {}", self.max_consecutive));
+        }
+        f.write_str(&clauses.join("; "))
+    }
+}
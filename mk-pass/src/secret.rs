@@ -0,0 +1,119 @@
+use std::fmt::Debug;
+
+use rand::prelude::*;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{CharacterSets, PasswordRequirements};
+
+/// A secret string (like a generated password) that is wiped from memory when dropped.
+///
+/// The [`Debug`] implementation does not reveal the secret, and
+/// there is no [`Display`](std::fmt::Display) implementation.
+/// Use [`SecretString::expose_secret()`] to access the secret.
+///
+/// ```rust
+/// use mk_pass::SecretString;
+/// let secret = SecretString::from("hunter2".to_string());
+/// assert_eq!(format!("{secret:?}"), "SecretString([REDACTED])");
+/// assert_eq!(secret.expose_secret(), "hunter2");
+/// ```
+#[derive(Clone, Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Take ownership of the given `secret`.
+    ///
+    /// The `secret` is not copied, so only its current allocation is wiped on drop.
+    pub fn new(secret: String) -> Self {
+        Self(secret)
+    }
+
+    /// Get the secret.
+    ///
+    /// Any copy made from the returned value is not wiped.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
+
+impl Zeroize for SecretString {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretString {}
+
+/// Generate a password given the constraints specified by `config`.
+///
+/// This behaves like [`generate_password()`](crate::generate_password), but the password
+/// is returned as a [`SecretString`] that is wiped from memory when dropped.
+/// The intermediate buffers used to generate the password are also wiped.
+///
+/// ```rust
+/// use mk_pass::{PasswordRequirements, generate_secret_password};
+/// let password = generate_secret_password(PasswordRequirements::default());
+/// assert_eq!(password.expose_secret().len(), 16);
+/// ```
+pub fn generate_secret_password(config: PasswordRequirements) -> SecretString {
+    CharacterSets::default().generate_secret_password(config)
+}
+
+/// Generate a password given the constraints specified by `config` using the given `rng`.
+///
+/// See also [`generate_secret_password()`].
+pub fn generate_secret_password_with_rng<R: CryptoRng + Rng>(
+    config: PasswordRequirements,
+    rng: &mut R,
+) -> SecretString {
+    CharacterSets::default().generate_secret_password_with_rng(config, rng)
+}
+
+impl CharacterSets {
+    /// Generate a password from these character sets given the constraints specified by `config`.
+    ///
+    /// See also [`generate_secret_password()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if both the [`CharacterSets::uppercase()`] and [`CharacterSets::lowercase()`]
+    /// sets are empty.
+    pub fn generate_secret_password(&self, config: PasswordRequirements) -> SecretString {
+        self.generate_secret_password_with_rng(config, &mut rand::rng())
+    }
+
+    /// Generate a password from these character sets given the constraints specified by `config`
+    /// using the given `rng`.
+    ///
+    /// See also [`generate_secret_password()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if both the [`CharacterSets::uppercase()`] and [`CharacterSets::lowercase()`]
+    /// sets are empty.
+    pub fn generate_secret_password_with_rng<R: CryptoRng + Rng>(
+        &self,
+        config: PasswordRequirements,
+        rng: &mut R,
+    ) -> SecretString {
+        SecretString::new(self.generate_password_with_rng(config, rng))
+    }
+}